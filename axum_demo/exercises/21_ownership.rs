@@ -0,0 +1,24 @@
+// 第21课练习：所有权转移
+// s1 传给 calculate_length 后所有权已经移走，不能再返回 s1
+// 修改 run() 让编译通过、测试通过，不要修改 calculate_length 和测试
+
+fn calculate_length(s: String) -> (String, usize) {
+    let length = s.len();
+    (s, length)
+}
+
+pub fn run() -> (String, usize) {
+    let s1 = String::from("hello");
+    let (_, len) = calculate_length(s1);
+    (s1, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_string_and_length() {
+        assert_eq!(run(), ("hello".to_string(), 5));
+    }
+}
@@ -0,0 +1,23 @@
+// 第23课练习：可变借用
+// append_world 需要修改传入的字符串
+// 修改函数签名和调用处让编译通过、测试通过，不要修改测试
+
+pub fn append_world(s: &String) {
+    s.push_str(", world");
+}
+
+pub fn greeting() -> String {
+    let s = String::from("hello");
+    append_world(&s);
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_in_place() {
+        assert_eq!(greeting(), "hello, world");
+    }
+}
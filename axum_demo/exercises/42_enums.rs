@@ -0,0 +1,28 @@
+// 第42课练习：用 match 处理枚举
+// 补全 area()，让每种形状返回正确的面积，不要修改测试
+
+pub enum Shape {
+    Circle { radius: f64 },
+    Rectangle { width: f64, height: f64 },
+    Triangle { base: f64, height: f64 },
+}
+
+pub fn area(shape: &Shape) -> f64 {
+    match shape {
+        Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+        Shape::Rectangle { width, height } => width * height,
+        // TODO: 处理 Triangle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_areas() {
+        assert_eq!(area(&Shape::Rectangle { width: 2.0, height: 3.0 }), 6.0);
+        assert_eq!(area(&Shape::Triangle { base: 4.0, height: 3.0 }), 6.0);
+        assert!((area(&Shape::Circle { radius: 1.0 }) - std::f64::consts::PI).abs() < 1e-9);
+    }
+}
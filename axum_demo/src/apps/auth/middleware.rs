@@ -0,0 +1,61 @@
+// Bearer 令牌认证：中间件校验令牌，AuthUser 提取器把调用者交给 handler
+use std::sync::Arc;
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header::AUTHORIZATION, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::service::AuthError;
+use super::token::JwtKeys;
+use crate::error::AppError;
+
+// 当前登录用户
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AuthUser {
+    pub id: Uuid,
+    pub username: String,
+}
+
+// 用法: router.layer(middleware::from_fn_with_state(keys, require_auth))
+pub async fn require_auth(
+    State(keys): State<Arc<JwtKeys>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(AuthError::MissingToken)?;
+
+    let claims = keys.verify(token.trim()).map_err(|e| {
+        tracing::debug!("令牌校验失败: {}", e);
+        AuthError::InvalidToken
+    })?;
+
+    request.extensions_mut().insert(AuthUser {
+        id: claims.sub,
+        username: claims.username,
+    });
+    Ok(next.run(request).await)
+}
+
+// 只能用于 require_auth 保护的路由，否则一律 401
+impl<S: Send + Sync> FromRequestParts<S> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthUser>()
+            .cloned()
+            .ok_or(AppError::Auth(AuthError::MissingToken))
+    }
+}
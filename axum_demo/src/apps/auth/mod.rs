@@ -0,0 +1,8 @@
+pub mod middleware;
+pub mod model;
+pub mod password;
+pub mod repository;
+pub mod router;
+pub mod service;
+pub mod table;
+pub mod token;
@@ -0,0 +1,56 @@
+// 输入输出模型，字段与 docs/auth.md 保持一致
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::validation::not_blank;
+
+// 用户名只允许字母、数字和 _ . -
+static USERNAME: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[A-Za-z0-9_.-]+$").unwrap());
+
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct Credentials {
+    // 用户名
+    #[serde(default)]
+    #[schema(required = true, min_length = 2, max_length = 64, pattern = r"^[A-Za-z0-9_.-]+$")]
+    #[validate(
+        custom(function = "not_blank", code = "required", message = "用户名不能为空"),
+        length(min = 2, max = 64, message = "用户名长度应为 2 到 64 个字符"),
+        regex(path = *USERNAME, message = "用户名只能包含字母、数字和 _ . -")
+    )]
+    pub username: String,
+    // 密码
+    #[serde(default)]
+    #[schema(required = true, min_length = 3, max_length = 128)]
+    #[validate(
+        custom(function = "not_blank", code = "required", message = "密码不能为空"),
+        length(min = 3, max = 128, message = "密码长度应为 3 到 128 个字符")
+    )]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenResponse {
+    // 访问令牌
+    pub token: String,
+    // 过期时间 (秒)
+    pub expires: u64,
+}
+
+// docs/auth.md 约定的错误响应 (application/json)，登录和注册的 404/401/409 使用
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+// 存储中的用户，只保存密码哈希
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct User {
+    pub id: Uuid,
+    pub username: String,
+    pub password_hash: String,
+}
@@ -0,0 +1,111 @@
+// 用户存储，与 NoteRepository 相同的抽象方式
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use sqlx::{PgPool, SqlitePool};
+use uuid::Uuid;
+
+use super::model::User;
+use crate::db::{Database, RepositoryError};
+
+#[async_trait]
+pub trait UserRepository: Send + Sync {
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, RepositoryError>;
+    // 用户名已存在时返回 RepositoryError::Conflict
+    async fn create(&self, username: &str, password_hash: &str) -> Result<User, RepositoryError>;
+}
+
+pub fn user_repository(db: &Database) -> Arc<dyn UserRepository> {
+    match db {
+        Database::Memory => Arc::new(MemoryUserRepository::default()),
+        Database::Sqlite(pool) => Arc::new(SqliteUserRepository { pool: pool.clone() }),
+        Database::Postgres(pool) => Arc::new(PgUserRepository { pool: pool.clone() }),
+    }
+}
+
+// 以用户名为键
+#[derive(Default)]
+pub struct MemoryUserRepository {
+    users: Arc<Mutex<HashMap<String, User>>>,
+}
+
+#[async_trait]
+impl UserRepository for MemoryUserRepository {
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, RepositoryError> {
+        let users = self.users.lock().unwrap();
+        Ok(users.get(username).cloned())
+    }
+
+    async fn create(&self, username: &str, password_hash: &str) -> Result<User, RepositoryError> {
+        let mut users = self.users.lock().unwrap();
+        if users.contains_key(username) {
+            return Err(RepositoryError::Conflict);
+        }
+        let user = User {
+            id: Uuid::new_v4(),
+            username: username.to_string(),
+            password_hash: password_hash.to_string(),
+        };
+        users.insert(user.username.clone(), user.clone());
+        Ok(user)
+    }
+}
+
+pub struct SqliteUserRepository {
+    pool: SqlitePool,
+}
+
+#[async_trait]
+impl UserRepository for SqliteUserRepository {
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, RepositoryError> {
+        let user = sqlx::query_as("SELECT id, username, password_hash FROM users WHERE username = ?")
+            .bind(username)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(user)
+    }
+
+    async fn create(&self, username: &str, password_hash: &str) -> Result<User, RepositoryError> {
+        let user = sqlx::query_as(
+            "INSERT INTO users (id, username, password_hash) VALUES (?, ?, ?)
+             RETURNING id, username, password_hash",
+        )
+        .bind(Uuid::new_v4())
+        .bind(username)
+        .bind(password_hash)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(user)
+    }
+}
+
+pub struct PgUserRepository {
+    pool: PgPool,
+}
+
+#[async_trait]
+impl UserRepository for PgUserRepository {
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, RepositoryError> {
+        let user = sqlx::query_as("SELECT id, username, password_hash FROM users WHERE username = $1")
+            .bind(username)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(user)
+    }
+
+    async fn create(&self, username: &str, password_hash: &str) -> Result<User, RepositoryError> {
+        let user = sqlx::query_as(
+            "INSERT INTO users (id, username, password_hash) VALUES ($1, $2, $3)
+             RETURNING id, username, password_hash",
+        )
+        .bind(Uuid::new_v4())
+        .bind(username)
+        .bind(password_hash)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(user)
+    }
+}
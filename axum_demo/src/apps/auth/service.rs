@@ -0,0 +1,86 @@
+// 登录与注册
+use std::sync::Arc;
+
+use super::model::{TokenResponse, User};
+use super::password::{hash_password, verify_password};
+use super::repository::UserRepository;
+use super::token::JwtKeys;
+use crate::db::RepositoryError;
+
+#[derive(Debug)]
+pub enum AuthError {
+    UserNotFound,
+    InvalidCredentials,
+    UsernameTaken,
+    // 请求没有 Authorization: Bearer 头
+    MissingToken,
+    // 签名、过期时间、iss 或 aud 校验失败
+    InvalidToken,
+    Repository(RepositoryError),
+    Hash(String),
+    Token(jsonwebtoken::errors::Error),
+}
+
+impl From<RepositoryError> for AuthError {
+    fn from(e: RepositoryError) -> Self {
+        match e {
+            RepositoryError::Conflict => AuthError::UsernameTaken,
+            e => AuthError::Repository(e),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AuthService {
+    repo: Arc<dyn UserRepository>,
+    keys: Arc<JwtKeys>,
+}
+
+impl AuthService {
+    pub fn new(repo: Arc<dyn UserRepository>, keys: Arc<JwtKeys>) -> Self {
+        AuthService { repo, keys }
+    }
+
+    pub async fn login(&self, username: &str, password: &str) -> Result<TokenResponse, AuthError> {
+        let user = self
+            .repo
+            .find_by_username(username)
+            .await?
+            .ok_or(AuthError::UserNotFound)?;
+
+        // Argon2 计算较慢，放到阻塞线程池中执行
+        let password = password.to_string();
+        let password_hash = user.password_hash.clone();
+        let verified = tokio::task::spawn_blocking(move || verify_password(&password, &password_hash))
+            .await
+            .map_err(|e| AuthError::Hash(e.to_string()))?;
+        if !verified {
+            return Err(AuthError::InvalidCredentials);
+        }
+
+        tracing::info!(user_id = %user.id, "用户登录");
+        self.token(&user)
+    }
+
+    pub async fn signup(&self, username: &str, password: &str) -> Result<TokenResponse, AuthError> {
+        if self.repo.find_by_username(username).await?.is_some() {
+            return Err(AuthError::UsernameTaken);
+        }
+
+        let password = password.to_string();
+        let password_hash = tokio::task::spawn_blocking(move || hash_password(&password))
+            .await
+            .map_err(|e| AuthError::Hash(e.to_string()))?
+            .map_err(|e| AuthError::Hash(e.to_string()))?;
+
+        // 并发注册同名用户时由唯一约束兜底，转换为 UsernameTaken
+        let user = self.repo.create(username, &password_hash).await?;
+
+        tracing::info!(user_id = %user.id, "用户注册");
+        self.token(&user)
+    }
+
+    fn token(&self, user: &User) -> Result<TokenResponse, AuthError> {
+        self.keys.issue(user).map_err(AuthError::Token)
+    }
+}
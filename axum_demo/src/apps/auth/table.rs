@@ -0,0 +1,29 @@
+// 用户表 (sql_table)
+use crate::db::{Database, RepositoryError};
+
+const CREATE_TABLE_SQLITE: &str = "
+CREATE TABLE IF NOT EXISTS users (
+    id            BLOB PRIMARY KEY NOT NULL,
+    username      VARCHAR(64) NOT NULL UNIQUE,
+    password_hash TEXT NOT NULL
+)";
+
+const CREATE_TABLE_POSTGRES: &str = "
+CREATE TABLE IF NOT EXISTS users (
+    id            UUID PRIMARY KEY,
+    username      VARCHAR(64) NOT NULL UNIQUE,
+    password_hash TEXT NOT NULL
+)";
+
+pub async fn create_table(db: &Database) -> Result<(), RepositoryError> {
+    match db {
+        Database::Memory => {}
+        Database::Sqlite(pool) => {
+            sqlx::query(CREATE_TABLE_SQLITE).execute(pool).await?;
+        }
+        Database::Postgres(pool) => {
+            sqlx::query(CREATE_TABLE_POSTGRES).execute(pool).await?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,153 @@
+// JWT 访问令牌：HS256 签名，校验签名、过期时间、iss 和 aud
+use jsonwebtoken::{decode, encode, get_current_timestamp, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::model::{TokenResponse, User};
+use crate::settings::AuthSettings;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    // 用户 id
+    pub sub: Uuid,
+    pub username: String,
+    pub iss: String,
+    pub aud: String,
+    pub iat: u64,
+    pub exp: u64,
+}
+
+pub struct JwtKeys {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    validation: Validation,
+    issuer: String,
+    audience: String,
+    ttl_secs: u64,
+}
+
+impl JwtKeys {
+    pub fn new(settings: &AuthSettings) -> Self {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_issuer(&[&settings.issuer]);
+        validation.set_audience(&[&settings.audience]);
+        validation.set_required_spec_claims(&["exp", "sub", "iss", "aud"]);
+
+        JwtKeys {
+            encoding: EncodingKey::from_secret(settings.jwt_secret.as_bytes()),
+            decoding: DecodingKey::from_secret(settings.jwt_secret.as_bytes()),
+            validation,
+            issuer: settings.issuer.clone(),
+            audience: settings.audience.clone(),
+            ttl_secs: settings.token_ttl_secs,
+        }
+    }
+
+    pub fn issue(&self, user: &User) -> Result<TokenResponse, jsonwebtoken::errors::Error> {
+        let now = get_current_timestamp();
+        let claims = Claims {
+            sub: user.id,
+            username: user.username.clone(),
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            iat: now,
+            exp: now + self.ttl_secs,
+        };
+        let token = encode(&Header::new(Algorithm::HS256), &claims, &self.encoding)?;
+
+        Ok(TokenResponse {
+            token,
+            expires: self.ttl_secs,
+        })
+    }
+
+    pub fn verify(&self, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+        let data = decode::<Claims>(token, &self.decoding, &self.validation)?;
+        Ok(data.claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonwebtoken::{encode, get_current_timestamp, EncodingKey, Header};
+    use serde::Serialize;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::{Claims, JwtKeys};
+    use crate::apps::auth::model::User;
+    use crate::apps::testing::auth_settings;
+
+    // 有效的声明，测试中逐项改坏
+    fn claims() -> Claims {
+        let settings = auth_settings();
+        let now = get_current_timestamp();
+        Claims {
+            sub: Uuid::new_v4(),
+            username: "aa".to_string(),
+            iss: settings.issuer,
+            aud: settings.audience,
+            iat: now,
+            exp: now + 60,
+        }
+    }
+
+    fn sign(claims: &impl Serialize, secret: &str) -> String {
+        encode(&Header::default(), claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap()
+    }
+
+    #[test]
+    fn issued_tokens_verify() {
+        let keys = JwtKeys::new(&auth_settings());
+        let user = User {
+            id: Uuid::new_v4(),
+            username: "aa".to_string(),
+            password_hash: String::new(),
+        };
+        let token = keys.issue(&user).unwrap();
+        assert_eq!(token.expires, auth_settings().token_ttl_secs);
+
+        let claims = keys.verify(&token.token).unwrap();
+        assert_eq!(claims.sub, user.id);
+        assert_eq!(claims.username, "aa");
+    }
+
+    #[test]
+    fn invalid_claims_are_rejected() {
+        let keys = JwtKeys::new(&auth_settings());
+        let secret = auth_settings().jwt_secret;
+
+        // 超过默认 60 秒的容差
+        let expired = Claims {
+            exp: get_current_timestamp() - 120,
+            ..claims()
+        };
+        let wrong_issuer = Claims {
+            iss: "someone-else".to_string(),
+            ..claims()
+        };
+        let wrong_audience = Claims {
+            aud: "another-api".to_string(),
+            ..claims()
+        };
+        let mut without_sub = serde_json::to_value(claims()).unwrap();
+        without_sub.as_object_mut().unwrap().remove("sub");
+        let mut bad_sub = serde_json::to_value(claims()).unwrap();
+        bad_sub["sub"] = json!("aa");
+        let tokens = [
+            ("过期", sign(&expired, &secret)),
+            ("iss 不符", sign(&wrong_issuer, &secret)),
+            ("aud 不符", sign(&wrong_audience, &secret)),
+            ("缺少 sub", sign(&without_sub, &secret)),
+            ("sub 不是 uuid", sign(&bad_sub, &secret)),
+            ("签名错误", sign(&claims(), "another-secret-with-at-least-32-bytes")),
+            ("格式错误", "not-a-jwt".to_string()),
+        ];
+
+        // 同样的声明用正确的密钥签名可以通过
+        assert!(keys.verify(&sign(&claims(), &secret)).is_ok());
+        for (case, token) in tokens {
+            assert!(keys.verify(&token).is_err(), "{}", case);
+        }
+    }
+}
@@ -1,20 +1,134 @@
-use axum::{routing::get, Json, Router};
-use crate::apps::note::router::router as note_router;
+use std::sync::Arc;
 
+use axum::{http::HeaderName, middleware, routing::get, Json, Router};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use serde::Serialize;
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi, ToSchema,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::apps::auth::{
+    repository::user_repository, router::{protected, router as auth_router},
+    service::AuthService, token::JwtKeys,
+};
+use crate::apps::note::{repository::note_repository, router::router as note_router, service::NoteService};
+use crate::db::{Database, RepositoryError};
+use crate::error::{problem_details, REQUEST_ID_HEADER};
+use crate::settings::AuthSettings;
+
+pub mod auth;
 pub mod note;
+#[cfg(test)]
+pub mod testing;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Note API", description = "Note management API with Axum", license(name = "MIT")),
+    tags(
+        (name = "App", description = "Application info"),
+        (name = auth::router::TAG, description = "Login and signup, see docs/auth.md"),
+        (name = note::router::TAG, description = "Note management operations"),
+    ),
+    modifiers(&SecurityAddon)
+)]
+struct ApiDoc;
+
+// 注册 Bearer JWT 安全方案，受保护的路由由 auth::router::protected 引用
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            auth::router::BEARER,
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
 
-#[derive(serde::Serialize)]
-struct User {
+#[derive(Serialize, ToSchema)]
+pub struct User {
     id: i32,
 }
 
+#[utoipa::path(get, path = "/", tag = "App", responses((status = 200, description = "欢迎信息", body = String)))]
+async fn root() -> &'static str {
+    "Hello from Apps!"
+}
+
+#[utoipa::path(get, path = "/user", tag = "App", responses((status = 200, description = "示例用户", body = User)))]
 async fn get_user() -> Json<User> {
     Json(User { id: 1 })
 }
 
-pub fn create_router() -> Router {
-    Router::new()
-        .route("/", get(|| async { "Hello from Apps!" }))
-        .route("/user", get(get_user))
-        .nest("/note", note_router())
-}
\ No newline at end of file
+// 所有带 OpenAPI 注解的路由
+pub fn api_router(db: &Database, auth: &AuthSettings) -> OpenApiRouter {
+    let keys = Arc::new(JwtKeys::new(auth));
+    let auth_service = AuthService::new(user_repository(db), keys.clone());
+
+    // 笔记接口需要登录
+    let notes = protected(note_router(NoteService::new(note_repository(db))), keys.clone());
+
+    OpenApiRouter::with_openapi(ApiDoc::openapi())
+        .routes(routes!(root))
+        .routes(routes!(get_user))
+        .nest("/api", auth_router(auth_service, keys))
+        .nest("/note", notes)
+}
+
+// 不需要连接数据库即可生成文档，与服务器使用同一份路由
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    api_router(&Database::Memory, &AuthSettings::default()).into_openapi()
+}
+
+// API 路由 + /openapi.json + /docs (Scalar)
+// 每个请求带上 x-request-id (客户端未提供时生成)，错误统一为 problem+json
+pub fn create_router(db: &Database, auth: &AuthSettings) -> Router {
+    let (router, openapi) = api_router(db, auth).split_for_parts();
+    let spec = openapi.clone();
+    let request_id = HeaderName::from_static(REQUEST_ID_HEADER);
+
+    router
+        .route("/openapi.json", get(|| async move { Json(spec) }))
+        .merge(Scalar::with_url("/docs", openapi))
+        .layer(middleware::from_fn(problem_details))
+        .layer(PropagateRequestIdLayer::new(request_id.clone()))
+        .layer(SetRequestIdLayer::new(request_id, MakeRequestUuid))
+}
+
+// 建表，启动服务器前调用
+pub async fn migrate(db: &Database) -> Result<(), RepositoryError> {
+    auth::table::create_table(db).await?;
+    note::table::create_table(db).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{Method, StatusCode};
+
+    use super::testing::TestApp;
+
+    #[tokio::test]
+    async fn serves_the_generated_spec() {
+        let app = TestApp::memory().await;
+        let response = app.request(Method::GET, "/openapi.json", None, None).await;
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.body, serde_json::to_value(super::openapi()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn serves_the_scalar_reference() {
+        let app = TestApp::memory().await;
+        let response = app.request(Method::GET, "/docs", None, None).await;
+        assert_eq!(response.status, StatusCode::OK);
+        assert!(response.header("content-type").unwrap().starts_with("text/html"));
+    }
+}
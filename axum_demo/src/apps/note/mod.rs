@@ -1 +1,5 @@
-pub mod router;
\ No newline at end of file
+pub mod model;
+pub mod repository;
+pub mod router;
+pub mod service;
+pub mod table;
@@ -0,0 +1,69 @@
+// 输入输出模型 (io_model)
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::validation::not_blank;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct Note {
+    pub id: Uuid,
+    pub title: String,
+    pub content: String,
+}
+
+// 缺少的字段按空字符串处理，交给 required 规则报告，而不是整体反序列化失败
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct NoteInsert {
+    #[serde(default)]
+    #[schema(required = true, min_length = 1, max_length = 255)]
+    #[validate(
+        custom(function = "not_blank", code = "required", message = "标题不能为空"),
+        length(max = 255, message = "标题不能超过 255 个字符")
+    )]
+    pub title: String,
+    #[serde(default)]
+    #[schema(required = true, min_length = 1, max_length = 2048)]
+    #[validate(
+        custom(function = "not_blank", code = "required", message = "内容不能为空"),
+        length(max = 2048, message = "内容不能超过 2048 个字符")
+    )]
+    pub content: String,
+}
+
+// 只更新请求中出现的字段，出现时规则与创建相同 (validator 对 Some 中的值执行校验)
+#[derive(Debug, Default, Deserialize, ToSchema, Validate)]
+pub struct NoteUpdate {
+    #[schema(min_length = 1, max_length = 255)]
+    #[validate(
+        custom(function = "not_blank", code = "required", message = "标题不能为空"),
+        length(max = 255, message = "标题不能超过 255 个字符")
+    )]
+    pub title: Option<String>,
+    #[schema(min_length = 1, max_length = 2048)]
+    #[validate(
+        custom(function = "not_blank", code = "required", message = "内容不能为空"),
+        length(max = 2048, message = "内容不能超过 2048 个字符")
+    )]
+    pub content: Option<String>,
+}
+
+impl Note {
+    pub fn new(data: NoteInsert) -> Self {
+        Note {
+            id: Uuid::new_v4(),
+            title: data.title,
+            content: data.content,
+        }
+    }
+
+    pub fn apply(&mut self, data: NoteUpdate) {
+        if let Some(title) = data.title {
+            self.title = title;
+        }
+        if let Some(content) = data.content {
+            self.content = content;
+        }
+    }
+}
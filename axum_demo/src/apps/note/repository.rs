@@ -0,0 +1,186 @@
+// 笔记存储抽象，handler 通过 State 依赖 trait 而不是具体后端
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use async_trait::async_trait;
+use sqlx::{PgPool, SqlitePool};
+use uuid::Uuid;
+
+use super::model::{Note, NoteInsert, NoteUpdate};
+use crate::db::{Database, RepositoryError};
+
+#[async_trait]
+pub trait NoteRepository: Send + Sync {
+    async fn list(&self) -> Result<Vec<Note>, RepositoryError>;
+    async fn get(&self, id: Uuid) -> Result<Option<Note>, RepositoryError>;
+    async fn create(&self, data: NoteInsert) -> Result<Note, RepositoryError>;
+    async fn update(&self, id: Uuid, data: NoteUpdate) -> Result<Option<Note>, RepositoryError>;
+    async fn delete(&self, id: Uuid) -> Result<Option<Note>, RepositoryError>;
+}
+
+pub fn note_repository(db: &Database) -> Arc<dyn NoteRepository> {
+    match db {
+        Database::Memory => Arc::new(MemoryNoteRepository::default()),
+        Database::Sqlite(pool) => Arc::new(SqliteNoteRepository { pool: pool.clone() }),
+        Database::Postgres(pool) => Arc::new(PgNoteRepository { pool: pool.clone() }),
+    }
+}
+
+// 内存实现：与第88课的 UserStore 相同的 Arc<Mutex<HashMap>> 模式
+// 每条笔记带插入序号，列表按序号返回，与数据库后端的顺序一致
+#[derive(Default)]
+pub struct MemoryNoteRepository {
+    notes: Arc<Mutex<HashMap<Uuid, (u64, Note)>>>,
+    next_seq: AtomicU64,
+}
+
+#[async_trait]
+impl NoteRepository for MemoryNoteRepository {
+    async fn list(&self) -> Result<Vec<Note>, RepositoryError> {
+        let notes = self.notes.lock().unwrap();
+        let mut list: Vec<&(u64, Note)> = notes.values().collect();
+        list.sort_by_key(|(seq, _)| *seq);
+        Ok(list.into_iter().map(|(_, note)| note.clone()).collect())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<Note>, RepositoryError> {
+        let notes = self.notes.lock().unwrap();
+        Ok(notes.get(&id).map(|(_, note)| note.clone()))
+    }
+
+    async fn create(&self, data: NoteInsert) -> Result<Note, RepositoryError> {
+        let note = Note::new(data);
+        let mut notes = self.notes.lock().unwrap();
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        notes.insert(note.id, (seq, note.clone()));
+        Ok(note)
+    }
+
+    async fn update(&self, id: Uuid, data: NoteUpdate) -> Result<Option<Note>, RepositoryError> {
+        let mut notes = self.notes.lock().unwrap();
+        Ok(notes.get_mut(&id).map(|(_, note)| {
+            note.apply(data);
+            note.clone()
+        }))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<Option<Note>, RepositoryError> {
+        let mut notes = self.notes.lock().unwrap();
+        Ok(notes.remove(&id).map(|(_, note)| note))
+    }
+}
+
+pub struct SqliteNoteRepository {
+    pool: SqlitePool,
+}
+
+#[async_trait]
+impl NoteRepository for SqliteNoteRepository {
+    async fn list(&self) -> Result<Vec<Note>, RepositoryError> {
+        let notes = sqlx::query_as("SELECT id, title, content FROM note ORDER BY rowid")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(notes)
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<Note>, RepositoryError> {
+        let note = sqlx::query_as("SELECT id, title, content FROM note WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(note)
+    }
+
+    async fn create(&self, data: NoteInsert) -> Result<Note, RepositoryError> {
+        let note = sqlx::query_as(
+            "INSERT INTO note (id, title, content) VALUES (?, ?, ?) RETURNING id, title, content",
+        )
+        .bind(Uuid::new_v4())
+        .bind(data.title)
+        .bind(data.content)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(note)
+    }
+
+    async fn update(&self, id: Uuid, data: NoteUpdate) -> Result<Option<Note>, RepositoryError> {
+        let note = sqlx::query_as(
+            "UPDATE note SET title = COALESCE(?, title), content = COALESCE(?, content)
+             WHERE id = ? RETURNING id, title, content",
+        )
+        .bind(data.title)
+        .bind(data.content)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(note)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<Option<Note>, RepositoryError> {
+        let note = sqlx::query_as("DELETE FROM note WHERE id = ? RETURNING id, title, content")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(note)
+    }
+}
+
+pub struct PgNoteRepository {
+    pool: PgPool,
+}
+
+#[async_trait]
+impl NoteRepository for PgNoteRepository {
+    async fn list(&self) -> Result<Vec<Note>, RepositoryError> {
+        let notes = sqlx::query_as("SELECT id, title, content FROM note ORDER BY seq")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(notes)
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<Note>, RepositoryError> {
+        let note = sqlx::query_as("SELECT id, title, content FROM note WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(note)
+    }
+
+    async fn create(&self, data: NoteInsert) -> Result<Note, RepositoryError> {
+        let note = sqlx::query_as(
+            "INSERT INTO note (id, title, content) VALUES ($1, $2, $3) RETURNING id, title, content",
+        )
+        .bind(Uuid::new_v4())
+        .bind(data.title)
+        .bind(data.content)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(note)
+    }
+
+    async fn update(&self, id: Uuid, data: NoteUpdate) -> Result<Option<Note>, RepositoryError> {
+        let note = sqlx::query_as(
+            "UPDATE note SET title = COALESCE($2, title), content = COALESCE($3, content)
+             WHERE id = $1 RETURNING id, title, content",
+        )
+        .bind(id)
+        .bind(data.title)
+        .bind(data.content)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(note)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<Option<Note>, RepositoryError> {
+        let note = sqlx::query_as("DELETE FROM note WHERE id = $1 RETURNING id, title, content")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(note)
+    }
+}
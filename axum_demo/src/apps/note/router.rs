@@ -1,7 +1,268 @@
-use axum::{routing::{get, post}, Router};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
 
-pub fn router() -> Router {
-    Router::new()
-    .route("/", get(|| async { "get note" }))
-    .route("/", post(|| async { "create note" }))
-}
\ No newline at end of file
+use super::model::{Note, NoteInsert, NoteUpdate};
+use super::service::NoteService;
+use crate::apps::auth::middleware::AuthUser;
+use crate::error::{AppError, ProblemDetails};
+use crate::validation::ValidatedJson;
+
+pub const TAG: &str = "Note";
+
+pub fn router(service: NoteService) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .routes(routes!(get_all_notes, create_note))
+        .routes(routes!(get_note, update_note, delete_note))
+        .with_state(service)
+}
+
+#[utoipa::path(
+    get,
+    path = "",
+    tag = TAG,
+    summary = "获取所有笔记",
+    responses(
+        (status = 200, description = "笔记列表", body = Vec<Note>),
+        (status = 401, description = "缺少令牌或令牌无效", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "存储错误", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn get_all_notes(State(service): State<NoteService>) -> Result<Json<Vec<Note>>, AppError> {
+    let notes = service.get_all_notes().await?;
+    Ok(Json(notes))
+}
+
+#[utoipa::path(
+    post,
+    path = "",
+    tag = TAG,
+    summary = "创建笔记",
+    request_body = NoteInsert,
+    responses(
+        (status = 201, description = "创建成功", body = Note),
+        (status = 401, description = "缺少令牌或令牌无效", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "请求参数校验失败", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "存储错误", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn create_note(
+    user: AuthUser,
+    State(service): State<NoteService>,
+    ValidatedJson(data): ValidatedJson<NoteInsert>,
+) -> Result<(StatusCode, Json<Note>), AppError> {
+    let note = service.create_note(data).await?;
+    tracing::info!(user = %user.username, note_id = %note.id, "创建笔记");
+    Ok((StatusCode::CREATED, Json(note)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = TAG,
+    summary = "获取指定笔记",
+    params(("id" = Uuid, Path, description = "笔记 id")),
+    responses(
+        (status = 200, description = "笔记", body = Note),
+        (status = 404, description = "笔记不存在", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 401, description = "缺少令牌或令牌无效", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "存储错误", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn get_note(
+    State(service): State<NoteService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Note>, AppError> {
+    let note = service.get_note_by_id(id).await?;
+    note.map(Json).ok_or_else(not_found)
+}
+
+#[utoipa::path(
+    patch,
+    path = "/{id}",
+    tag = TAG,
+    summary = "部分更新笔记",
+    params(("id" = Uuid, Path, description = "笔记 id")),
+    request_body = NoteUpdate,
+    responses(
+        (status = 200, description = "更新后的笔记", body = Note),
+        (status = 404, description = "笔记不存在", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 401, description = "缺少令牌或令牌无效", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "请求参数校验失败", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "存储错误", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn update_note(
+    user: AuthUser,
+    State(service): State<NoteService>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(data): ValidatedJson<NoteUpdate>,
+) -> Result<Json<Note>, AppError> {
+    let note = service.update_note(id, data).await?;
+    tracing::info!(user = %user.username, note_id = %id, "更新笔记");
+    note.map(Json).ok_or_else(not_found)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = TAG,
+    summary = "删除笔记",
+    params(("id" = Uuid, Path, description = "笔记 id")),
+    responses(
+        (status = 200, description = "被删除的笔记", body = Note),
+        (status = 404, description = "笔记不存在", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 401, description = "缺少令牌或令牌无效", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "存储错误", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn delete_note(
+    user: AuthUser,
+    State(service): State<NoteService>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Note>, AppError> {
+    let note = service.delete_note(id).await?;
+    tracing::info!(user = %user.username, note_id = %id, "删除笔记");
+    note.map(Json).ok_or_else(not_found)
+}
+
+fn not_found() -> AppError {
+    AppError::NotFound("笔记不存在".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{Method, StatusCode};
+    use serde_json::json;
+
+    use crate::apps::testing::TestApp;
+    use crate::db::Database;
+    use crate::error::PROBLEM_JSON;
+
+    // 每次连接都是一个新的内存数据库
+    async fn sqlite() -> TestApp {
+        TestApp::new(Database::connect("sqlite::memory:").await.unwrap()).await
+    }
+
+    // 创建、列表、获取、更新、删除，各存储后端共用
+    async fn crud(app: TestApp) {
+        let token = app.token("aa");
+        let token = Some(token.as_str());
+
+        let created = app
+            .request(Method::POST, "/note", token, Some(json!({"title": "t1", "content": "c1"})))
+            .await;
+        assert_eq!(created.status, StatusCode::CREATED);
+        assert_eq!(created.body["title"], "t1");
+        let path = format!("/note/{}", created.body["id"].as_str().unwrap());
+
+        let list = app.request(Method::GET, "/note", token, None).await;
+        assert_eq!(list.status, StatusCode::OK);
+        assert_eq!(list.body, json!([created.body]));
+
+        let note = app.request(Method::GET, &path, token, None).await;
+        assert_eq!((note.status, &note.body), (StatusCode::OK, &created.body));
+
+        // 只更新出现的字段
+        let updated = app
+            .request(Method::PATCH, &path, token, Some(json!({"content": "c2"})))
+            .await;
+        assert_eq!(updated.status, StatusCode::OK);
+        assert_eq!((&updated.body["title"], &updated.body["content"]), (&json!("t1"), &json!("c2")));
+
+        let deleted = app.request(Method::DELETE, &path, token, None).await;
+        assert_eq!((deleted.status, &deleted.body), (StatusCode::OK, &updated.body));
+
+        let list = app.request(Method::GET, "/note", token, None).await;
+        assert_eq!(list.body, json!([]));
+    }
+
+    // 列表按创建顺序返回，更新不改变位置
+    async fn list_order(app: TestApp) {
+        let token = app.token("aa");
+        let token = Some(token.as_str());
+        let mut ids = Vec::new();
+        for index in 0..5 {
+            let body = json!({"title": format!("t{}", index), "content": "c"});
+            let created = app.request(Method::POST, "/note", token, Some(body)).await;
+            ids.push(created.body["id"].clone());
+        }
+        let path = |id: &serde_json::Value| format!("/note/{}", id.as_str().unwrap());
+        app.request(Method::PATCH, &path(&ids[0]), token, Some(json!({"title": "changed"})))
+            .await;
+        app.request(Method::DELETE, &path(&ids[2]), token, None).await;
+        ids.remove(2);
+
+        let list = app.request(Method::GET, "/note", token, None).await;
+        let listed: Vec<_> = list.body.as_array().unwrap().iter().map(|note| note["id"].clone()).collect();
+        assert_eq!(listed, ids);
+    }
+
+    // 不存在的 id 在获取、更新、删除时都返回 404
+    async fn missing_note(app: TestApp) {
+        let token = app.token("aa");
+        let token = Some(token.as_str());
+        let path = format!("/note/{}", uuid::Uuid::new_v4());
+
+        let requests = [
+            (Method::GET, None),
+            (Method::PATCH, Some(json!({"title": "t"}))),
+            (Method::DELETE, None),
+        ];
+        for (method, body) in requests {
+            let response = app.request(method.clone(), &path, token, body).await;
+            assert_eq!(response.status, StatusCode::NOT_FOUND, "{} {}", method, path);
+            assert_eq!(response.body["detail"], "笔记不存在");
+        }
+    }
+
+    #[tokio::test]
+    async fn note_crud_with_memory_repository() {
+        crud(TestApp::memory().await).await;
+    }
+
+    #[tokio::test]
+    async fn missing_note_is_404_with_memory_repository() {
+        missing_note(TestApp::memory().await).await;
+    }
+
+    #[tokio::test]
+    async fn notes_are_listed_in_creation_order_with_memory_repository() {
+        list_order(TestApp::memory().await).await;
+    }
+
+    #[tokio::test]
+    async fn notes_are_listed_in_creation_order_with_sqlite_repository() {
+        list_order(sqlite().await).await;
+    }
+
+    #[tokio::test]
+    async fn note_crud_with_sqlite_repository() {
+        crud(sqlite().await).await;
+    }
+
+    #[tokio::test]
+    async fn missing_note_is_404_with_sqlite_repository() {
+        missing_note(sqlite().await).await;
+    }
+
+    #[tokio::test]
+    async fn notes_require_a_token() {
+        let app = TestApp::memory().await;
+        let response = app.request(Method::GET, "/note", None, None).await;
+        assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn invalid_note_id_is_400() {
+        let app = TestApp::memory().await;
+        let token = app.token("aa");
+        let response = app.request(Method::GET, "/note/not-a-uuid", Some(&token), None).await;
+        assert_eq!(response.status, StatusCode::BAD_REQUEST);
+        assert_eq!(response.header("content-type"), Some(PROBLEM_JSON));
+    }
+}
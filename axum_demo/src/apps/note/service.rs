@@ -0,0 +1,43 @@
+// 笔记业务逻辑，handler 只负责 HTTP 相关的部分
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use super::model::{Note, NoteInsert, NoteUpdate};
+use super::repository::NoteRepository;
+use crate::db::RepositoryError;
+
+#[derive(Clone)]
+pub struct NoteService {
+    repo: Arc<dyn NoteRepository>,
+}
+
+impl NoteService {
+    pub fn new(repo: Arc<dyn NoteRepository>) -> Self {
+        NoteService { repo }
+    }
+
+    pub async fn get_all_notes(&self) -> Result<Vec<Note>, RepositoryError> {
+        self.repo.list().await
+    }
+
+    pub async fn get_note_by_id(&self, id: Uuid) -> Result<Option<Note>, RepositoryError> {
+        self.repo.get(id).await
+    }
+
+    pub async fn create_note(&self, data: NoteInsert) -> Result<Note, RepositoryError> {
+        self.repo.create(data).await
+    }
+
+    pub async fn update_note(
+        &self,
+        id: Uuid,
+        data: NoteUpdate,
+    ) -> Result<Option<Note>, RepositoryError> {
+        self.repo.update(id, data).await
+    }
+
+    pub async fn delete_note(&self, id: Uuid) -> Result<Option<Note>, RepositoryError> {
+        self.repo.delete(id).await
+    }
+}
@@ -0,0 +1,35 @@
+// 笔记表 (sql_table)
+use crate::db::{Database, RepositoryError};
+
+// 列表按插入顺序返回：SQLite 使用隐式的 rowid (主键不是 INTEGER，rowid 单独递增)，
+// PostgreSQL 使用 seq 列；早期建的表没有 seq，建表后补上
+const CREATE_TABLE_SQLITE: &str = "
+CREATE TABLE IF NOT EXISTS note (
+    id      BLOB PRIMARY KEY NOT NULL,
+    title   VARCHAR(255) NOT NULL,
+    content VARCHAR(2048) NOT NULL
+)";
+
+const CREATE_TABLE_POSTGRES: &str = "
+CREATE TABLE IF NOT EXISTS note (
+    id      UUID PRIMARY KEY,
+    title   VARCHAR(255) NOT NULL,
+    content VARCHAR(2048) NOT NULL,
+    seq     BIGSERIAL
+)";
+
+const ADD_SEQ_POSTGRES: &str = "ALTER TABLE note ADD COLUMN IF NOT EXISTS seq BIGSERIAL";
+
+pub async fn create_table(db: &Database) -> Result<(), RepositoryError> {
+    match db {
+        Database::Memory => {}
+        Database::Sqlite(pool) => {
+            sqlx::query(CREATE_TABLE_SQLITE).execute(pool).await?;
+        }
+        Database::Postgres(pool) => {
+            sqlx::query(CREATE_TABLE_POSTGRES).execute(pool).await?;
+            sqlx::query(ADD_SEQ_POSTGRES).execute(pool).await?;
+        }
+    }
+    Ok(())
+}
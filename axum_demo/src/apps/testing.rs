@@ -0,0 +1,94 @@
+// 测试辅助：构建与服务器相同的路由 (create_router)，不监听端口，直接发送请求
+use axum::{
+    body::{to_bytes, Body},
+    http::{header::AUTHORIZATION, header::CONTENT_TYPE, HeaderMap, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use super::auth::{model::User, token::JwtKeys};
+use crate::db::Database;
+use crate::settings::AuthSettings;
+
+pub fn auth_settings() -> AuthSettings {
+    AuthSettings {
+        jwt_secret: "test-secret-with-at-least-32-bytes".to_string(),
+        token_ttl_secs: 60,
+        issuer: "axum_demo".to_string(),
+        audience: "axum_demo-api".to_string(),
+    }
+}
+
+pub struct TestApp {
+    router: Router,
+}
+
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    // 响应体不是 JSON 时为 Null
+    pub body: Value,
+}
+
+impl TestResponse {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+}
+
+impl TestApp {
+    pub async fn new(db: Database) -> Self {
+        super::migrate(&db).await.unwrap();
+        TestApp {
+            router: super::create_router(&db, &auth_settings()),
+        }
+    }
+
+    pub async fn memory() -> Self {
+        Self::new(Database::Memory).await
+    }
+
+    // 不经过注册，直接签发令牌
+    pub fn token(&self, username: &str) -> String {
+        let user = User {
+            id: Uuid::new_v4(),
+            username: username.to_string(),
+            password_hash: String::new(),
+        };
+        JwtKeys::new(&auth_settings()).issue(&user).unwrap().token
+    }
+
+    pub async fn send(&self, request: Request<Body>) -> TestResponse {
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let (parts, body) = response.into_parts();
+        let bytes = to_bytes(body, usize::MAX).await.unwrap();
+        TestResponse {
+            status: parts.status,
+            headers: parts.headers,
+            body: serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        }
+    }
+
+    pub async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        token: Option<&str>,
+        body: Option<Value>,
+    ) -> TestResponse {
+        let mut request = Request::builder().method(method).uri(path);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let body = match body {
+            Some(body) => {
+                request = request.header(CONTENT_TYPE, "application/json");
+                Body::from(body.to_string())
+            }
+            None => Body::empty(),
+        };
+        self.send(request.body(body).unwrap()).await
+    }
+}
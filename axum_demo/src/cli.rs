@@ -0,0 +1,249 @@
+// 命令行定义
+// 子命令、参数和帮助文本都由 clap 根据这里的结构体生成，
+// 不再手写 usage，避免帮助与实际行为不一致；帮助文本在 command() 中按 --lang 翻译
+//
+// 退出码 (供脚本判断):
+//   0 - 成功
+//   1 - 运行失败 (服务器启动失败、数据库不可用、写文件失败等)
+//   2 - 命令行用法错误 (未知命令/参数、课程不存在)，与 clap 默认一致
+//   3 - 配置无效
+
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+
+use crate::i18n::{self, Lang};
+use crate::learn::{book, output, LESSONS};
+use crate::openapi;
+use crate::settings::{self, SettingsError, DEFAULT_CONFIG_DIR, LOG_LEVELS};
+
+pub const EXIT_FAILURE: u8 = 1;
+pub const EXIT_USAGE: u8 = 2;
+pub const EXIT_CONFIG: u8 = 3;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "axum_demo",
+    version,
+    about = "Rust 学习程序：课程示例 + Axum Web 服务",
+    arg_required_else_help = true,
+    after_help = "示例:\n  axum_demo learn 01                 运行第1课：变量与类型\n  axum_demo web --port 3000          在 3000 端口启动 Web 服务器\n  axum_demo openapi --out openapi.yaml\n  axum_demo migrate --config ./config"
+)]
+pub struct Cli {
+    /// 日志级别，优先于 RUST_LOG 和配置中的 log.level
+    #[arg(long, global = true, value_name = "LEVEL", value_parser = LOG_LEVELS)]
+    pub log_level: Option<String>,
+
+    /// 配置目录
+    #[arg(long, global = true, value_name = "DIR", default_value = DEFAULT_CONFIG_DIR)]
+    pub config: String,
+
+    /// 界面语言，默认按 LC_ALL / LC_MESSAGES / LANG 选择
+    #[arg(long, global = true, value_enum)]
+    pub lang: Option<Lang>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 运行指定课程，不带编号时列出所有课程
+    Learn(LearnArgs),
+    /// 启动 Web 服务器
+    Web(WebArgs),
+    /// 导出 OpenAPI 文档 (不启动服务器)
+    Openapi {
+        /// 输出格式，默认 json，或按 --out 的扩展名推断
+        #[arg(long, value_enum)]
+        format: Option<openapi::Format>,
+        /// 写入文件，默认输出到标准输出
+        #[arg(long, short, value_name = "FILE")]
+        out: Option<String>,
+    },
+    /// 连接数据库并建表，然后退出
+    Migrate {
+        /// 覆盖配置项，可重复，如 --set database.url=sqlite://notes.db
+        #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_override)]
+        overrides: Vec<(String, String)>,
+    },
+    /// 输出 shell 补全脚本，如 axum_demo completions bash > ~/.local/share/bash-completion/completions/axum_demo
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// 输出 roff 格式的 man 手册，如 axum_demo manpage > axum_demo.1
+    Manpage,
+}
+
+#[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct LearnArgs {
+    /// 课程编号，如 01、21、88
+    #[arg(value_parser = lesson_ids())]
+    pub id: Option<String>,
+    /// 输出 Markdown 学习清单 (README 中的学习进度跟踪)
+    #[arg(long, conflicts_with = "id")]
+    pub checklist: bool,
+    /// 打开全屏终端界面浏览和运行课程
+    #[arg(long, conflicts_with_all = ["id", "checklist"])]
+    pub tui: bool,
+    /// 启动课程中的示例服务器 (第88课)，端口默认 3000，可用 PORT 环境变量修改
+    #[arg(long, requires = "id", conflicts_with = "format")]
+    pub serve: bool,
+    /// 输出格式，默认在终端中使用颜色 (设置 NO_COLOR 可关闭)
+    #[arg(long, value_enum)]
+    pub format: Option<output::Format>,
+
+    #[command(subcommand)]
+    pub action: Option<LearnAction>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum LearnAction {
+    /// 按阶段查看学习进度
+    Progress {
+        /// 输出格式，默认在终端中使用颜色 (设置 NO_COLOR 可关闭)
+        #[arg(long, value_enum)]
+        format: Option<output::Format>,
+    },
+    /// 运行下一门未完成的课程
+    Next {
+        /// 输出格式，默认在终端中使用颜色 (设置 NO_COLOR 可关闭)
+        #[arg(long, value_enum)]
+        format: Option<output::Format>,
+    },
+    /// 清除学习进度
+    Reset,
+    /// 完成指定课程的练习 (选择题 + 修改代码)
+    Exercise {
+        /// 课程编号
+        #[arg(value_parser = lesson_ids())]
+        id: String,
+        /// 把代码练习恢复为初始内容
+        #[arg(long)]
+        reset: bool,
+    },
+    /// 把全部课程导出为静态课程手册，含示例输出和语言对比
+    Export {
+        /// 手册格式
+        #[arg(long, value_enum, default_value = "html")]
+        format: book::Format,
+        /// 输出目录，不存在时自动创建
+        #[arg(long, short, value_name = "DIR", default_value = "book")]
+        out: PathBuf,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct WebArgs {
+    /// 监听地址 host:port，等价于同时指定 --host 和 --port
+    #[arg(value_name = "ADDR", conflicts_with_all = ["host", "port"])]
+    pub addr: Option<String>,
+    /// 监听主机，覆盖 server.host
+    #[arg(long)]
+    pub host: Option<String>,
+    /// 监听端口，覆盖 server.port
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub port: Option<u16>,
+    /// 覆盖配置项，可重复，如 --set log.level=debug
+    #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_override)]
+    pub overrides: Vec<(String, String)>,
+}
+
+impl WebArgs {
+    // 转换为配置覆盖，--set 在前，专用参数优先
+    pub fn into_overrides(self) -> Result<Vec<(String, String)>, String> {
+        let mut overrides = self.overrides;
+        if let Some(addr) = self.addr {
+            let (host, port) = addr
+                .rsplit_once(':')
+                .ok_or_else(|| i18n::tr!("无效的监听地址: {}，应为 host:port", addr))?;
+            overrides.push(("server.host".to_string(), host.to_string()));
+            overrides.push(("server.port".to_string(), port.to_string()));
+        }
+        if let Some(host) = self.host {
+            overrides.push(("server.host".to_string(), host));
+        }
+        if let Some(port) = self.port {
+            overrides.push(("server.port".to_string(), port.to_string()));
+        }
+        Ok(overrides)
+    }
+}
+
+// 课程编号的可选值来自课程列表，补全脚本中会带上课程标题
+fn lesson_ids() -> PossibleValuesParser {
+    PossibleValuesParser::new(
+        LESSONS
+            .iter()
+            .map(|lesson| PossibleValue::new(lesson.id()).help(lesson.title())),
+    )
+}
+
+fn parse_override(arg: &str) -> Result<(String, String), String> {
+    settings::parse_override(arg).ok_or_else(|| i18n::lookup("应为 key=value 形式").to_string())
+}
+
+// 按当前语言翻译帮助文本后的命令定义，解析参数、补全和 man 手册都使用它
+pub fn command() -> clap::Command {
+    localize(Cli::command())
+}
+
+fn localize(mut command: clap::Command) -> clap::Command {
+    if let Some(about) = command.get_about() {
+        let about = i18n::text(&about.to_string());
+        command = command.about(about);
+    }
+    if let Some(after_help) = command.get_after_help() {
+        let after_help = i18n::text(&after_help.to_string());
+        command = command.after_help(after_help);
+    }
+    command
+        .mut_args(|arg| match arg.get_help() {
+            Some(help) => {
+                let help = i18n::text(&help.to_string());
+                arg.help(help)
+            }
+            None => arg,
+        })
+        .mut_subcommands(localize)
+}
+
+// 失败时打印原因并返回对应的退出码，message 已按当前语言生成 (i18n::tr!)
+pub fn fail(code: u8, message: &str) -> ExitCode {
+    eprintln!("❌ {}", message);
+    ExitCode::from(code)
+}
+
+pub fn config_error(e: SettingsError) -> ExitCode {
+    fail(EXIT_CONFIG, &e.to_string())
+}
+
+pub fn print_completions(shell: Shell) {
+    let mut command = command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name.clone(), &mut io::stdout());
+
+    // fish 的生成器不会补全位置参数的可选值，这里补上课程编号
+    if shell == Shell::Fish {
+        let ids: Vec<String> = LESSONS
+            .iter()
+            .map(|lesson| format!("{}\\t'{}'", lesson.id(), lesson.title()))
+            .collect();
+        println!(
+            "complete -c {} -n \"__fish_{}_using_subcommand learn\" -f -a \"{}\"",
+            name,
+            name,
+            ids.join(" ")
+        );
+    }
+}
+
+pub fn print_manpage() -> io::Result<()> {
+    clap_mangen::Man::new(command()).render(&mut io::stdout())
+}
@@ -0,0 +1,121 @@
+// 数据库连接：根据 DATABASE_URL 的 scheme 选择存储后端
+//   memory://                        - 内存存储，进程退出即丢失（测试、演示）
+//   sqlite://notes.db                - 嵌入式 SQLite，文件不存在时自动创建
+//   postgres://aa@localhost/web_demo - PostgreSQL
+
+use std::{fmt, str::FromStr, time::Duration};
+
+use sqlx::{
+    postgres::{PgPool, PgPoolOptions},
+    sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions},
+};
+
+#[derive(Clone)]
+pub enum Database {
+    Memory,
+    Sqlite(SqlitePool),
+    Postgres(PgPool),
+}
+
+impl Database {
+    pub async fn connect(url: &str) -> Result<Self, RepositoryError> {
+        if url.starts_with("memory:") {
+            return Ok(Database::Memory);
+        }
+
+        if url.starts_with("sqlite:") {
+            let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+            let pool = SqlitePoolOptions::new().connect_with(options).await?;
+            return Ok(Database::Sqlite(pool));
+        }
+
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(Duration::from_secs(5))
+            .connect(url)
+            .await?;
+        Ok(Database::Postgres(pool))
+    }
+
+    pub fn backend(&self) -> &'static str {
+        match self {
+            Database::Memory => "memory",
+            Database::Sqlite(_) => "sqlite",
+            Database::Postgres(_) => "postgres",
+        }
+    }
+}
+
+// 存储层错误，与具体后端无关
+#[derive(Debug)]
+pub enum RepositoryError {
+    // 违反唯一约束，如用户名重复
+    Conflict,
+    Database(sqlx::Error),
+}
+
+impl fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RepositoryError::Conflict => write!(f, "记录已存在"),
+            RepositoryError::Database(e) => write!(f, "数据库错误: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RepositoryError::Conflict => None,
+            RepositoryError::Database(e) => Some(e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for RepositoryError {
+    fn from(e: sqlx::Error) -> Self {
+        match e.as_database_error() {
+            Some(db_err) if db_err.is_unique_violation() => RepositoryError::Conflict,
+            _ => RepositoryError::Database(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn sqlite() -> SqlitePool {
+        match Database::connect("sqlite::memory:").await.unwrap() {
+            Database::Sqlite(pool) => pool,
+            db => panic!("sqlite::memory: 连接到了 {}", db.backend()),
+        }
+    }
+
+    #[tokio::test]
+    async fn memory_url_needs_no_connection() {
+        let db = Database::connect("memory://").await.unwrap();
+        assert_eq!(db.backend(), "memory");
+    }
+
+    #[tokio::test]
+    async fn unique_violation_is_conflict() {
+        let pool = sqlite().await;
+        sqlx::query("CREATE TABLE t (name TEXT NOT NULL UNIQUE)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let insert = || sqlx::query("INSERT INTO t (name) VALUES ('aa')").execute(&pool);
+
+        insert().await.unwrap();
+        let error = RepositoryError::from(insert().await.unwrap_err());
+        assert!(matches!(error, RepositoryError::Conflict), "{:?}", error);
+    }
+
+    #[tokio::test]
+    async fn other_database_errors_are_kept() {
+        let pool = sqlite().await;
+        let error = sqlx::query("SELECT * FROM missing").execute(&pool).await.unwrap_err();
+        let error = RepositoryError::from(error);
+        assert!(matches!(error, RepositoryError::Database(_)), "{:?}", error);
+    }
+}
@@ -0,0 +1,400 @@
+// 统一错误类型，响应为 RFC 7807 application/problem+json
+// handler 返回 Result<T, AppError>，存储层、认证等错误通过 From 自动转换:
+//
+//   {
+//     "type": "/problems/not-found",
+//     "title": "Not Found",
+//     "status": 404,
+//     "detail": "笔记不存在",
+//     "instance": "/note/3fa3...",
+//     "request_id": "5b1c..."
+//   }
+//
+// instance 和 request_id 由 problem_details 中间件在响应返回前补充；
+// 5xx 的具体原因只写入日志，不返回给客户端
+// 例外: docs/auth.md 中登录和注册的 404/401/409 按文档返回 application/json {"error": ...}
+
+use std::fmt;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{
+        header::{CONTENT_LENGTH, CONTENT_TYPE, WWW_AUTHENTICATE},
+        HeaderValue, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::apps::auth::model::ErrorResponse;
+use crate::apps::auth::service::AuthError;
+use crate::db::RepositoryError;
+use crate::validation::FieldError;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// 框架生成的纯文本错误体最多读取的字节数
+const MAX_PLAIN_ERROR_BYTES: usize = 64 * 1024;
+
+#[derive(Debug)]
+pub enum AppError {
+    NotFound(String),
+    Conflict(String),
+    // 提取器拒绝的请求，如 JSON 格式错误 (400)、Content-Type 不对 (415)
+    Rejected(StatusCode, String),
+    // 请求体校验失败 (422)，列出每个字段和规则
+    Validation(Vec<FieldError>),
+    // 认证错误，docs/auth.md 中约定的几种按文档格式返回
+    Auth(AuthError),
+    // 原因只记录到日志
+    Internal(String),
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    #[schema(example = "/problems/not-found")]
+    pub kind: String,
+    #[schema(example = "Not Found")]
+    pub title: String,
+    #[schema(example = 404)]
+    pub status: u16,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    // 校验失败的字段，仅 422 返回
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+// 随响应传给中间件的 5xx 原因
+#[derive(Clone)]
+struct InternalCause(String);
+
+impl AppError {
+    pub fn internal(cause: impl fmt::Display) -> Self {
+        AppError::Internal(cause.to_string())
+    }
+
+    fn status(&self) -> StatusCode {
+        match self {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::Rejected(status, _) => *status,
+            AppError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::Auth(e) => match e {
+                AuthError::UserNotFound => StatusCode::NOT_FOUND,
+                AuthError::InvalidCredentials | AuthError::MissingToken | AuthError::InvalidToken => {
+                    StatusCode::UNAUTHORIZED
+                }
+                AuthError::UsernameTaken => StatusCode::CONFLICT,
+                AuthError::Repository(_) | AuthError::Hash(_) | AuthError::Token(_) => {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+            },
+            AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            AppError::NotFound(detail) | AppError::Conflict(detail) | AppError::Rejected(_, detail) => {
+                detail.clone()
+            }
+            AppError::Validation(errors) => format!("请求参数校验失败 ({} 项)", errors.len()),
+            AppError::Auth(e) => match e {
+                AuthError::UserNotFound => "用户不存在".to_string(),
+                AuthError::InvalidCredentials => "密码或用户名错误".to_string(),
+                AuthError::UsernameTaken => "用户名已存在".to_string(),
+                AuthError::MissingToken => "缺少访问令牌".to_string(),
+                AuthError::InvalidToken => "访问令牌无效或已过期".to_string(),
+                AuthError::Repository(_) | AuthError::Hash(_) | AuthError::Token(_) => {
+                    internal_detail()
+                }
+            },
+            AppError::Internal(_) => internal_detail(),
+        }
+    }
+
+    // docs/auth.md 中约定了响应体的错误
+    fn documented(&self) -> bool {
+        matches!(
+            self,
+            AppError::Auth(AuthError::UserNotFound | AuthError::InvalidCredentials | AuthError::UsernameTaken)
+        )
+    }
+
+    fn cause(&self) -> Option<String> {
+        match self {
+            AppError::Internal(cause) => Some(cause.clone()),
+            AppError::Auth(AuthError::Repository(e)) => Some(e.to_string()),
+            AppError::Auth(AuthError::Hash(e)) => Some(format!("密码哈希出错: {}", e)),
+            AppError::Auth(AuthError::Token(e)) => Some(format!("令牌签发出错: {}", e)),
+            _ => None,
+        }
+    }
+}
+
+fn internal_detail() -> String {
+    "服务器内部错误".to_string()
+}
+
+impl ProblemDetails {
+    pub fn new(status: StatusCode, detail: impl Into<String>) -> Self {
+        let title = status.canonical_reason().unwrap_or("Error");
+        ProblemDetails {
+            kind: format!("/problems/{}", title.to_lowercase().replace(' ', "-")),
+            title: title.to_string(),
+            status: status.as_u16(),
+            detail: detail.into(),
+            instance: None,
+            request_id: None,
+            errors: Vec::new(),
+        }
+    }
+
+    fn into_response_with(self, status: StatusCode) -> Response {
+        let body = serde_json::to_vec(&self).unwrap_or_default();
+        let mut response = (status, body).into_response();
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.cause() {
+            Some(cause) => write!(f, "{}: {}", self.status(), cause),
+            None => write!(f, "{}: {}", self.status(), self.detail()),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        if self.documented() {
+            return (status, Json(ErrorResponse { error: self.detail() })).into_response();
+        }
+        let mut problem = ProblemDetails::new(status, self.detail());
+        if let AppError::Validation(errors) = &self {
+            problem.errors = errors.clone();
+        }
+
+        let mut response = problem.into_response_with(status);
+        if status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        if let Some(cause) = self.cause() {
+            response.extensions_mut().insert(InternalCause(cause));
+        }
+        response
+    }
+}
+
+impl From<RepositoryError> for AppError {
+    fn from(e: RepositoryError) -> Self {
+        match e {
+            RepositoryError::Conflict => AppError::Conflict("记录已存在".to_string()),
+            e => AppError::internal(e),
+        }
+    }
+}
+
+impl From<AuthError> for AppError {
+    fn from(e: AuthError) -> Self {
+        AppError::Auth(e)
+    }
+}
+
+// 补充 instance / request_id，记录 5xx 原因，并把框架生成的纯文本错误
+// (如 JSON 解析失败、路径参数无效、路由不存在) 转换为 problem+json
+pub async fn problem_details(request: Request, next: Next) -> Response {
+    let instance = request.uri().path().to_string();
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let method = request.method().clone();
+
+    let mut response = next.run(request).await;
+    let status = response.status();
+
+    let problem = match response.extensions_mut().remove::<ProblemDetails>() {
+        Some(problem) => problem,
+        None if is_plain_error(&response) => {
+            let (parts, body) = response.into_parts();
+            let text = to_bytes(body, MAX_PLAIN_ERROR_BYTES)
+                .await
+                .map(|bytes| String::from_utf8_lossy(&bytes).trim().to_string())
+                .unwrap_or_default();
+            response = Response::from_parts(parts, Body::empty());
+            let detail = if text.is_empty() {
+                status.canonical_reason().unwrap_or("Error").to_string()
+            } else {
+                text
+            };
+            ProblemDetails::new(status, detail)
+        }
+        None => return response,
+    };
+
+    if let Some(InternalCause(cause)) = response.extensions_mut().remove::<InternalCause>() {
+        tracing::error!(
+            request_id = request_id.as_deref().unwrap_or("-"),
+            %method,
+            path = %instance,
+            "{} {}",
+            status,
+            cause
+        );
+    }
+
+    let problem = ProblemDetails {
+        instance: Some(instance),
+        request_id,
+        ..problem
+    };
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(CONTENT_LENGTH);
+    parts
+        .headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+    Response::from_parts(parts, Body::from(serde_json::to_vec(&problem).unwrap_or_default()))
+}
+
+// 4xx/5xx 且不是 JSON 的响应
+fn is_plain_error(response: &Response) -> bool {
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) {
+        return false;
+    }
+    match response.headers().get(CONTENT_TYPE) {
+        None => true,
+        Some(value) => value
+            .to_str()
+            .map(|ct| ct.starts_with("text/plain"))
+            .unwrap_or(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Method, Request, StatusCode},
+        middleware,
+        routing::get,
+        Router,
+    };
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::apps::testing::TestApp;
+
+    #[tokio::test]
+    async fn instance_and_client_request_id_are_filled_in() {
+        let app = TestApp::memory().await;
+        let request = Request::post("/api/login")
+            .header(CONTENT_TYPE, "application/json")
+            .header(REQUEST_ID_HEADER, "req-1")
+            .body(Body::from(r#"{"username": "aa"}"#))
+            .unwrap();
+        let response = app.send(request).await;
+
+        assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.header("content-type"), Some(PROBLEM_JSON));
+        assert_eq!(response.header(REQUEST_ID_HEADER), Some("req-1"));
+        assert_eq!(
+            response.body,
+            json!({
+                "type": "/problems/unprocessable-entity",
+                "title": "Unprocessable Entity",
+                "status": 422,
+                "detail": "请求参数校验失败 (1 项)",
+                "instance": "/api/login",
+                "request_id": "req-1",
+                "errors": [{"field": "password", "rule": "required", "message": "密码不能为空"}],
+            })
+        );
+    }
+
+    // 登录和注册的错误按 docs/auth.md 返回，不是 problem+json
+    #[tokio::test]
+    async fn documented_auth_errors_keep_their_body() {
+        let app = TestApp::memory().await;
+        let body = json!({"username": "nobody", "password": "aaa"});
+        let response = app.request(Method::POST, "/api/login", None, Some(body)).await;
+
+        assert_eq!(response.status, StatusCode::NOT_FOUND);
+        assert_eq!(response.header("content-type"), Some("application/json"));
+        assert_eq!(response.body, json!({"error": "用户不存在"}));
+    }
+
+    #[tokio::test]
+    async fn request_id_is_generated_when_missing() {
+        let app = TestApp::memory().await;
+        let response = app.request(Method::GET, "/note", None, None).await;
+        let request_id = response.header(REQUEST_ID_HEADER).unwrap();
+        assert!(uuid::Uuid::parse_str(request_id).is_ok(), "{}", request_id);
+        assert_eq!(response.body["request_id"], request_id);
+    }
+
+    // axum 的提取器拒绝和路由不存在返回纯文本或空响应体
+    #[tokio::test]
+    async fn plain_text_rejections_become_problem_json() {
+        let app = TestApp::memory().await;
+        let request = Request::post("/api/login")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from("{not json"))
+            .unwrap();
+        let malformed = app.send(request).await;
+        assert_eq!(malformed.status, StatusCode::BAD_REQUEST);
+        assert_eq!(malformed.header("content-type"), Some(PROBLEM_JSON));
+        assert_eq!(malformed.body["instance"], "/api/login");
+        assert!(
+            malformed.body["detail"].as_str().unwrap().contains("Failed to parse the request body as JSON"),
+            "{}",
+            malformed.body
+        );
+
+        let missing = app.request(Method::GET, "/no-such-route", None, None).await;
+        assert_eq!(missing.status, StatusCode::NOT_FOUND);
+        assert_eq!(missing.header("content-type"), Some(PROBLEM_JSON));
+        assert_eq!(missing.body["detail"], "Not Found");
+        assert_eq!(missing.body["instance"], "/no-such-route");
+    }
+
+    #[tokio::test]
+    async fn internal_cause_is_not_returned() {
+        let router = Router::new()
+            .route("/boom", get(|| async { Err::<(), _>(AppError::internal("连接池已耗尽")) }))
+            .layer(middleware::from_fn(problem_details));
+        let response = router
+            .oneshot(Request::get("/boom").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("服务器内部错误"), "{}", body);
+        assert!(!body.contains("连接池"), "{}", body);
+    }
+}
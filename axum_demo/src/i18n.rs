@@ -0,0 +1,472 @@
+// 界面语言 (--lang zh|en)
+// 中文是源语言，没有单独的中文目录：代码中的中文格式串就是消息的键，英文翻译在 locales/en.toml 中，
+// 按源文件分表，另有 [notes] 表保存各课末尾的语言对比说明 (以课程编号为键)
+// 不另设 zh.toml 和 "lesson61.file_missing" 这类稳定键，是因为课程会把自己的源码打印给学习者看：
+// 源码里必须是原样的中文句子，改成键名就看不懂了；zh.toml 也只会是键到自身的映射。
+// 键随原文变化带来的失效由 tests/i18n.rs 的双向检查兜住
+//
+// 课程代码保持普通的 println! / format! / write! 写法，只在输出处翻译：learn 模块的 println! / print!
+// 和 tr! 在格式化时用格式串本身查目录，并单独拿到每个参数格式化后的文本 (见 Arg)，填入英文模板；
+// 参数本身 (如 Err 中的错误信息、format! 拼好的句子) 按整句再查一次目录，
+// 整句不在目录中时按带参数的格式串反查 (见 Template::parse_args)。
+// 英文模板中 {} 按顺序对应中文格式串的参数，参数顺序不同时写 {0} {1}
+// 课程打印的源码 (listing::print_region) 只翻译其中的注释和字符串，键为注释文字或字符串字面量
+//
+// 语言优先级: --lang > LC_ALL > LC_MESSAGES > LANG，都未设置或为 C/POSIX 时使用中文
+// tests/i18n.rs 双向检查目录与代码：目录中的键都还在代码中，代码中的中文格式串都有翻译；
+// 并检查英文输出中是否还有未翻译的中文
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::LazyLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Lang {
+    // 中文 (默认)
+    Zh,
+    // English
+    En,
+}
+
+// 只有两种语言，用一个标记即可
+static ENGLISH: AtomicBool = AtomicBool::new(false);
+
+impl Lang {
+    // 按 locale 环境变量选择，zh_CN.UTF-8 等为中文，其他语言为英文
+    pub fn detect() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map_or(Lang::Zh, |value| Lang::from_locale(&value))
+    }
+
+    fn from_locale(locale: &str) -> Self {
+        match locale {
+            "C" | "POSIX" => Lang::Zh,
+            _ if locale.to_lowercase().starts_with("zh") => Lang::Zh,
+            _ if locale.starts_with("C.") => Lang::Zh,
+            _ => Lang::En,
+        }
+    }
+
+    // 命令行解析前从参数中找出 --lang，帮助文本需要在解析时就是目标语言；
+    // 无效的值留给 clap 报错
+    pub fn from_args(args: &[OsString]) -> Option<Self> {
+        let mut args = args.iter().filter_map(|arg| arg.to_str());
+        while let Some(arg) = args.next() {
+            let value = match arg.strip_prefix("--lang") {
+                Some("") => args.next(),
+                Some(rest) => rest.strip_prefix('='),
+                None => None,
+            };
+            match value {
+                Some("zh") => return Some(Lang::Zh),
+                Some("en") => return Some(Lang::En),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+pub fn set(lang: Lang) {
+    ENGLISH.store(lang == Lang::En, Ordering::Relaxed);
+}
+
+pub fn current() -> Lang {
+    if ENGLISH.load(Ordering::Relaxed) {
+        Lang::En
+    } else {
+        Lang::Zh
+    }
+}
+
+// 与 format! 相同，结果按当前语言翻译
+// 参数只能按位置传入；{name} 捕获的变量拿不到单独的文本，这时按整句翻译
+macro_rules! tr {
+    ($fmt:literal $(, $arg:expr)* $(,)?) => {
+        $crate::i18n::translate($fmt, ::std::format!($fmt $(, $crate::i18n::Arg(&$arg))*))
+    };
+}
+
+pub(crate) use tr;
+
+// 格式化结果中每个参数前后的标记 (Unicode 私用区字符)，translate 据此切出参数
+const ARG_START: char = '\u{e000}';
+const ARG_END: char = '\u{e001}';
+
+// 包装 tr! 的参数：按原来的格式说明格式化，前后写入标记
+pub struct Arg<'a, T: ?Sized>(pub &'a T);
+
+macro_rules! forward_fmt {
+    ($($trait:ident),*) => {$(
+        impl<T: fmt::$trait + ?Sized> fmt::$trait for Arg<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Write::write_char(f, ARG_START)?;
+                fmt::$trait::fmt(self.0, f)?;
+                fmt::Write::write_char(f, ARG_END)
+            }
+        }
+    )*};
+}
+
+forward_fmt!(Display, Debug, Binary, Octal, LowerHex, UpperHex, LowerExp, UpperExp, Pointer);
+
+// 翻译不带参数的固定文本，如课程标题
+pub fn lookup(text: &'static str) -> &'static str {
+    if current() == Lang::Zh {
+        return text;
+    }
+    CATALOG.fixed.get(text).map_or(text, String::as_str)
+}
+
+// 翻译任意文本：整句匹配目录中的固定文本，否则替换其中出现的已知短语
+pub fn text(text: &str) -> String {
+    if current() == Lang::Zh {
+        return text.to_string();
+    }
+    CATALOG.text(text)
+}
+
+// fmt 为中文格式串，marked 为 tr! 格式化后带参数标记的结果
+pub fn translate(fmt: &str, marked: String) -> String {
+    if current() == Lang::Zh {
+        return split_args(&marked).0;
+    }
+    CATALOG.translate(fmt, &marked)
+}
+
+// 去掉参数标记，返回 (格式化结果, 各参数的文本)
+fn split_args(marked: &str) -> (String, Vec<&str>) {
+    let mut text = String::with_capacity(marked.len());
+    let mut args = Vec::new();
+    let mut start = None;
+    for (index, c) in marked.char_indices() {
+        match c {
+            ARG_START => start = Some(index + c.len_utf8()),
+            ARG_END => args.extend(start.take().map(|start| &marked[start..index])),
+            c => text.push(c),
+        }
+    }
+    (text, args)
+}
+
+// 翻译一行源码中的字符串字面量和 // 注释，其余代码原样保留
+pub fn code(line: &str) -> String {
+    if current() == Lang::Zh || !has_chinese(line) {
+        return line.to_string();
+    }
+    let mut text = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find(['"', '/']) {
+        let (before, after) = rest.split_at(start);
+        text.push_str(before);
+        if let Some(comment) = after.strip_prefix("//") {
+            let body = comment.trim_start_matches('/').trim_start();
+            text.push_str(&after[..after.len() - body.len()]);
+            text.push_str(&CATALOG.code(body));
+            return text;
+        }
+        if let Some(after) = after.strip_prefix('/') {
+            text.push('/');
+            rest = after;
+            continue;
+        }
+        // 字符串字面量，跳过其中的转义字符
+        let mut end = 1;
+        let bytes = after.as_bytes();
+        while end < bytes.len() && bytes[end] != b'"' {
+            end += if bytes[end] == b'\\' { 2 } else { 1 };
+        }
+        if end >= bytes.len() {
+            // 跨行的字符串，剩余部分原样保留
+            text.push_str(after);
+            return text;
+        }
+        text.push('"');
+        text.push_str(&CATALOG.code(&after[1..end]));
+        text.push('"');
+        rest = &after[end + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+// 课程的语言对比说明
+pub fn note(id: &str) -> Option<&'static str> {
+    CATALOG.notes.get(id).map(String::as_str)
+}
+
+pub fn has_chinese(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(c, '\u{3000}'..='\u{303f}' | '\u{4e00}'..='\u{9fff}' | '\u{ff00}'..='\u{ffef}')
+    })
+}
+
+// 格式串拆成的文本片段与占位符，pieces 比 slots 多一个
+struct Template {
+    pieces: Vec<String>,
+    // 每个占位符对应的参数下标
+    slots: Vec<usize>,
+}
+
+impl Template {
+    fn parse(fmt: &str) -> Self {
+        let mut pieces = vec![String::new()];
+        let mut slots = Vec::new();
+        let mut chars = fmt.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    pieces.last_mut().unwrap().push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    pieces.last_mut().unwrap().push('}');
+                }
+                '{' => {
+                    let spec: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                    let name = spec.split(':').next().unwrap_or("");
+                    slots.push(name.parse().unwrap_or(slots.len()));
+                    pieces.push(String::new());
+                }
+                c => pieces.last_mut().unwrap().push(c),
+            }
+        }
+        Template { pieces, slots }
+    }
+
+    // 文本片段的总长度
+    fn literal_len(&self) -> usize {
+        self.pieces.iter().map(String::len).sum()
+    }
+
+    // render 的逆过程：text 由这个格式串格式化而来时，按占位符顺序返回各参数的文本
+    // 片段之间的参数取最短匹配
+    fn parse_args<'a>(&self, text: &'a str) -> Option<Vec<&'a str>> {
+        let (first, rest) = self.pieces.split_first()?;
+        let (last, middle) = rest.split_last()?;
+        let mut remaining = text.strip_prefix(first.as_str())?.strip_suffix(last.as_str())?;
+        let mut args = Vec::with_capacity(self.slots.len());
+        for piece in middle {
+            let end = remaining.find(piece.as_str())?;
+            args.push(&remaining[..end]);
+            remaining = &remaining[end + piece.len()..];
+        }
+        args.push(remaining);
+        Some(args)
+    }
+
+    fn render(&self, args: &[String]) -> String {
+        let mut text = self.pieces[0].clone();
+        for (slot, piece) in self.slots.iter().zip(&self.pieces[1..]) {
+            text.push_str(args.get(*slot).map_or("", String::as_str));
+            text.push_str(piece);
+        }
+        text
+    }
+}
+
+struct Message {
+    // 原始英文格式串，源码中的格式串按原样替换
+    value: String,
+    source: Template,
+    target: Template,
+}
+
+struct Catalog {
+    // 以中文格式串为键，tr! 和 learn 模块的 println! 等宏按格式串查找
+    messages: HashMap<String, Message>,
+    // 不带参数的消息，以展开 {{ }} 后的文本为键
+    fixed: HashMap<String, String>,
+    // fixed 按中文文本长度从长到短排列，替换句中短语时优先匹配更长的
+    phrases: Vec<(String, String)>,
+    // 带参数的消息，按文本片段总长度从长到短排列，用来反查已经格式化好的整句
+    patterns: Vec<String>,
+    notes: HashMap<String, String>,
+}
+
+static CATALOG: LazyLock<Catalog> = LazyLock::new(|| {
+    Catalog::parse(include_str!("../locales/en.toml")).expect("locales/en.toml 格式错误")
+});
+
+impl Catalog {
+    fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        let tables: HashMap<String, HashMap<String, String>> = toml::from_str(text)?;
+        let mut messages = HashMap::new();
+        let mut notes = HashMap::new();
+        for (name, table) in tables {
+            if name == "notes" {
+                notes = table;
+                continue;
+            }
+            messages.extend(table.into_iter().map(|(key, value)| {
+                let message = Message {
+                    source: Template::parse(&key),
+                    target: Template::parse(&value),
+                    value,
+                };
+                (key, message)
+            }));
+        }
+
+        let fixed: HashMap<String, String> = messages
+            .values()
+            .filter(|message| message.source.slots.is_empty())
+            .map(|message| (message.source.pieces[0].clone(), message.target.pieces[0].clone()))
+            .collect();
+        let mut phrases: Vec<(String, String)> = fixed.clone().into_iter().collect();
+        phrases.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        // 文本片段全为空的格式串 (如 "{}{}") 无法反查
+        let mut patterns: Vec<String> = messages
+            .iter()
+            .filter(|(_, message)| !message.source.slots.is_empty() && message.source.literal_len() > 0)
+            .map(|(key, _)| key.clone())
+            .collect();
+        patterns.sort_by_key(|key| (std::cmp::Reverse(messages[key].source.literal_len()), key.clone()));
+        Ok(Catalog {
+            messages,
+            fixed,
+            phrases,
+            patterns,
+            notes,
+        })
+    }
+
+    fn translate(&self, fmt: &str, marked: &str) -> String {
+        let (formatted, args) = split_args(marked);
+        if !has_chinese(&formatted) {
+            return formatted;
+        }
+        let message = self.messages.get(fmt);
+        let parsed;
+        let source = match message {
+            Some(message) => &message.source,
+            None => {
+                parsed = Template::parse(fmt);
+                &parsed
+            }
+        };
+        if args.len() != source.slots.len() {
+            return self.text(&formatted);
+        }
+
+        // 同一参数可以出现多次，按参数下标保存
+        let mut values = vec![String::new(); source.slots.iter().max().map_or(0, |max| max + 1)];
+        for (slot, arg) in source.slots.iter().zip(args) {
+            values[*slot] = self.text(arg);
+        }
+        match message {
+            Some(message) => message.target.render(&values),
+            // 格式串本身不需要翻译 (如 "{}: {}")，只翻译参数
+            None => source.render(&values),
+        }
+    }
+
+    // 源码中的格式串按原样换成英文格式串，不展开 {{ }}
+    fn code(&self, text: &str) -> String {
+        match self.messages.get(text) {
+            Some(message) => message.value.clone(),
+            None => self.text(text),
+        }
+    }
+
+    fn text(&self, text: &str) -> String {
+        if !has_chinese(text) {
+            return text.to_string();
+        }
+        if let Some(translation) = self.fixed.get(text) {
+            return translation.clone();
+        }
+        // 课程中用 format! / write! 拼好的句子 (如错误类型的 Display) 作为参数输出时，
+        // 按目录中的格式串反查出各参数，参数再逐个翻译
+        for key in &self.patterns {
+            let message = &self.messages[key];
+            if let Some(args) = message.source.parse_args(text) {
+                let mut values = vec![String::new(); message.source.slots.iter().max().map_or(0, |max| max + 1)];
+                for (slot, arg) in message.source.slots.iter().zip(args) {
+                    values[*slot] = self.text(arg);
+                }
+                return message.target.render(&values);
+            }
+        }
+        // 如 Debug 输出 Err("除数不能为零")，替换其中完整出现的消息
+        let mut text = text.to_string();
+        for (source, target) in &self.phrases {
+            if text.contains(source.as_str()) {
+                text = text.replace(source.as_str(), target);
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG_TOML: &str = r#"
+[test]
+"{}{}元" = "{} {} yuan"
+"{}的面积是 {:.2}" = "the area of {} is {}"
+"{1} 在 {0} 之后" = "{0} comes before {1}"
+"圆形" = "circle"
+"除数不能为零" = "division by zero"
+"tokio = {{ 版本 }}" = "tokio = {{ version }}"
+"#;
+
+    fn catalog() -> Catalog {
+        Catalog::parse(CATALOG_TOML).unwrap()
+    }
+
+    #[test]
+    fn adjacent_placeholders_are_split_by_markers() {
+        let marked = format!("{}{}元", Arg(&3), Arg(&"个"));
+        assert_eq!(split_args(&marked), ("3个元".to_string(), vec!["3", "个"]));
+        assert_eq!(catalog().translate("{}{}元", &marked), "3 个 yuan");
+    }
+
+    #[test]
+    fn args_keep_their_format_spec_and_are_translated() {
+        let marked = format!("{}的面积是 {:.2}", Arg(&"圆形"), Arg(&std::f64::consts::PI));
+        assert_eq!(catalog().translate("{}的面积是 {:.2}", &marked), "the area of circle is 3.14");
+    }
+
+    #[test]
+    fn positional_args_can_be_reordered() {
+        let marked = format!("{1} 在 {0} 之后", Arg(&"a"), Arg(&"b"));
+        assert_eq!(catalog().translate("{1} 在 {0} 之后", &marked), "a comes before b");
+    }
+
+    #[test]
+    fn unknown_format_string_only_translates_args() {
+        let marked = format!("结果: {:?}", Arg(&Err::<(), _>("除数不能为零")));
+        assert_eq!(catalog().translate("结果: {:?}", &marked), "结果: Err(\"division by zero\")");
+    }
+
+    // 课程中 format! 拼好的句子作为参数输出，按格式串反查出参数再翻译
+    #[test]
+    fn formatted_args_are_matched_against_templates() {
+        let catalog = catalog();
+        assert_eq!(catalog.text("圆形的面积是 3.14"), "the area of circle is 3.14");
+        assert_eq!(catalog.text("b 在 a 之后"), "a comes before b");
+        assert_eq!(catalog.text("正方形的面积"), "正方形的面积");
+    }
+
+    #[test]
+    fn fixed_text_is_looked_up_unescaped() {
+        let catalog = catalog();
+        assert_eq!(catalog.text("tokio = { 版本 }"), "tokio = { version }");
+        assert_eq!(catalog.code("tokio = {{ 版本 }}"), "tokio = {{ version }}");
+    }
+
+    #[test]
+    fn output_without_chinese_is_unchanged() {
+        let marked = format!("{:04b} {:p}", Arg(&5), Arg(&std::ptr::null::<u8>()));
+        assert_eq!(catalog().translate("{:04b} {:p}", &marked), "0101 0x0");
+    }
+}
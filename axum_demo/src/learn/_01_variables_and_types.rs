@@ -1,6 +1,18 @@
 // 01 - 变量与类型
 // 对比 C/Python/TypeScript 的变量声明
 
+use super::lesson::FnLesson;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "01",
+    title: "变量与类型",
+    summary: "let, mut, 类型推断",
+    source: include_str!("_01_variables_and_types.rs"),
+    run,
+    example: main_example,
+    exercises: &[],
+};
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 01. 变量与类型 ===");
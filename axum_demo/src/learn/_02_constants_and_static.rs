@@ -1,6 +1,18 @@
 // 02 - 常量与静态变量
 // 理解编译时常量和运行时静态变量
 
+use super::lesson::FnLesson;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "02",
+    title: "常量与静态变量",
+    summary: "const, static",
+    source: include_str!("_02_constants_and_static.rs"),
+    run,
+    example: main_example,
+    exercises: &[],
+};
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 02. 常量与静态变量 ===");
@@ -1,6 +1,18 @@
 // 03 - 基本操作符
 // 算术、比较、逻辑操作符及其特殊性
 
+use super::lesson::FnLesson;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "03",
+    title: "基本操作符",
+    summary: "算术、比较、逻辑操作",
+    source: include_str!("_03_basic_operators.rs"),
+    run,
+    example: main_example,
+    exercises: &[],
+};
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 03. 基本操作符 ===");
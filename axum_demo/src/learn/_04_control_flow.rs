@@ -1,6 +1,18 @@
 // 04 - 控制流
 // if/else、循环、模式匹配
 
+use super::lesson::FnLesson;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "04",
+    title: "控制流",
+    summary: "if, loop, while, for, match",
+    source: include_str!("_04_control_flow.rs"),
+    run,
+    example: main_example,
+    exercises: &[],
+};
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 04. 控制流 ===");
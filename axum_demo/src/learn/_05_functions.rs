@@ -1,6 +1,18 @@
 // 05 - 函数
 // 函数定义、参数、返回值、高阶函数
 
+use super::lesson::FnLesson;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "05",
+    title: "函数",
+    summary: "fn, 参数, 返回值, 闭包",
+    source: include_str!("_05_functions.rs"),
+    run,
+    example: main_example,
+    exercises: &[],
+};
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 05. 函数 ===");
@@ -1,6 +1,39 @@
 // 21 - 所有权基础
 // Rust 最重要的概念：所有权系统
 
+use super::exercise::{Choice, Exercise, Fix};
+use super::lesson::FnLesson;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "21",
+    title: "所有权基础",
+    summary: "移动语义, 克隆, 栈vs堆",
+    source: include_str!("_21_ownership_basics.rs"),
+    run,
+    example: main_example,
+    exercises: &EXERCISES,
+};
+
+const EXERCISES: [Exercise; 2] = [
+    Exercise::Choice(Choice {
+        question: "let s1 = String::from(\"hi\"); let s2 = s1; 之后哪一项正确？",
+        options: &[
+            "s1 和 s2 指向两份独立的堆数据",
+            "s1 的所有权移动到 s2，之后不能再使用 s1",
+            "s1 和 s2 共享同一份数据，都可以继续使用",
+            "编译错误，String 不能赋值给另一个变量",
+        ],
+        answer: 1,
+        explanation: "String 没有实现 Copy，赋值会移动所有权；需要两份数据时使用 s1.clone()。",
+    }),
+    Exercise::Fix(Fix {
+        file: "21_ownership.rs",
+        prompt: "修复编译错误：s1 的所有权已经移入 calculate_length",
+        code: include_str!("../../exercises/21_ownership.rs"),
+        hint: "calculate_length 会把 String 连同长度一起还回来，用解构接住它",
+    }),
+];
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 21. 所有权基础 ===");
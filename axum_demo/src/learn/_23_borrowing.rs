@@ -1,6 +1,39 @@
 // 23 - 借用与引用
 // 解决所有权转移问题的核心机制
 
+use super::exercise::{Choice, Exercise, Fix};
+use super::lesson::FnLesson;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "23",
+    title: "借用与引用",
+    summary: "&T, &mut T, 借用规则",
+    source: include_str!("_23_borrowing.rs"),
+    run,
+    example: main_example,
+    exercises: &EXERCISES,
+};
+
+const EXERCISES: [Exercise; 2] = [
+    Exercise::Choice(Choice {
+        question: "同一作用域内，以下哪种借用组合是允许的？",
+        options: &[
+            "一个 &mut T 和一个 &T 同时存在",
+            "两个 &mut T 同时存在",
+            "任意多个 &T 同时存在",
+            "&mut T 存在时仍可直接修改原变量",
+        ],
+        answer: 2,
+        explanation: "借用规则：要么任意多个不可变引用，要么只有一个可变引用。",
+    }),
+    Exercise::Fix(Fix {
+        file: "23_borrowing.rs",
+        prompt: "修复编译错误：append_world 需要修改传入的字符串",
+        code: include_str!("../../exercises/23_borrowing.rs"),
+        hint: "参数改为 &mut String，调用处变量声明为 mut 并传入 &mut s",
+    }),
+];
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 23. 借用与引用 ===");
@@ -1,6 +1,18 @@
 // 41 - 结构体
 // 自定义数据类型，类似其他语言的 class 或 struct
 
+use super::lesson::FnLesson;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "41",
+    title: "结构体",
+    summary: "struct, impl, 方法",
+    source: include_str!("_41_structs.rs"),
+    run,
+    example: main_example,
+    exercises: &[],
+};
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 41. 结构体 ===");
@@ -1,6 +1,39 @@
 // 42 - 枚举
 // 强大的代数数据类型，远超其他语言的枚举
 
+use super::exercise::{Choice, Exercise, Fix};
+use super::lesson::FnLesson;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "42",
+    title: "枚举",
+    summary: "enum, match, Option, Result",
+    source: include_str!("_42_enums.rs"),
+    run,
+    example: main_example,
+    exercises: &EXERCISES,
+};
+
+const EXERCISES: [Exercise; 2] = [
+    Exercise::Choice(Choice {
+        question: "match 没有覆盖枚举的所有变体时会发生什么？",
+        options: &[
+            "运行时遇到未覆盖的变体时 panic",
+            "编译错误：non-exhaustive patterns",
+            "未覆盖的变体被静默忽略",
+            "编译器自动补上 _ => {} 分支",
+        ],
+        answer: 1,
+        explanation: "match 必须穷尽所有可能，新增变体时编译器会指出每个需要处理的地方。",
+    }),
+    Exercise::Fix(Fix {
+        file: "42_enums.rs",
+        prompt: "补全 match：area() 还没有处理 Triangle",
+        code: include_str!("../../exercises/42_enums.rs"),
+        hint: "三角形面积为 base * height / 2.0，用 Shape::Triangle { base, height } 解构",
+    }),
+];
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 42. 枚举 ===");
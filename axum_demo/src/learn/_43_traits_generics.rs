@@ -0,0 +1,435 @@
+// 43 - Trait 与泛型
+// trait 描述共同行为，泛型让代码适用于多种类型；静态分发与动态分发的取舍
+
+use std::f64::consts::PI;
+use std::fmt;
+use std::hint::black_box;
+use std::time::Instant;
+
+use super::lesson::FnLesson;
+use super::listing::print_region;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "43",
+    title: "Trait 与泛型",
+    summary: "trait, 泛型约束, impl Trait, dyn Trait",
+    source: include_str!("_43_traits_generics.rs"),
+    run,
+    example: main_example,
+    exercises: &[],
+};
+
+#[allow(dead_code)]
+pub fn run() {
+    println!("=== 43. Trait 与泛型 ===");
+
+    // 1. 定义和实现 trait
+    trait_basics();
+
+    // 2. 关联类型
+    associated_types();
+
+    // 3. 泛型与约束
+    generics_and_bounds();
+
+    // 4. impl Trait
+    impl_trait();
+
+    // 5. dyn Trait
+    trait_objects();
+
+    // 6. 标准库中的常用 trait
+    standard_traits();
+}
+
+fn trait_basics() {
+    println!("--- 定义和实现 trait ---");
+
+    println!("trait 定义一组方法，可以带默认实现:");
+    print_region(LESSON.source, "shape");
+    let circle = Circle { radius: 1.0 };
+    let rectangle = Rectangle { width: 3.0, height: 4.0 };
+    println!("{}", circle.describe());
+    println!("{}", rectangle.describe());
+    println!();
+
+    println!("trait 可以继承 (supertrait)，实现 Solid 之前必须先实现 Shape:");
+    print_region(LESSON.source, "solid");
+    println!("圆柱体积 (高 2): {:.2}", circle.volume(2.0));
+    println!("长方体体积 (高 2): {:.2}", rectangle.volume(2.0));
+    println!("{}", rectangle.report(2.0));
+}
+
+// region: shape
+trait Shape {
+    fn name(&self) -> String;
+    fn area(&self) -> f64;
+
+    // 默认实现，可以调用 trait 中的其他方法
+    fn describe(&self) -> String {
+        format!("{}的面积是 {:.2}", self.name(), self.area())
+    }
+}
+
+struct Circle {
+    radius: f64,
+}
+
+struct Rectangle {
+    width: f64,
+    height: f64,
+}
+
+impl Shape for Circle {
+    fn name(&self) -> String {
+        "圆形".to_string()
+    }
+
+    fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+}
+
+impl Shape for Rectangle {
+    fn name(&self) -> String {
+        "矩形".to_string()
+    }
+
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    // 覆盖默认实现
+    fn describe(&self) -> String {
+        format!("{}x{} 的矩形，面积是 {:.2}", self.width, self.height, self.area())
+    }
+}
+// endregion: shape
+
+// region: solid
+trait Solid: Shape {
+    fn volume(&self, height: f64) -> f64 {
+        self.area() * height
+    }
+
+    fn report(&self, height: f64) -> String {
+        format!("{}拉伸 {} 后体积为 {:.2}", self.name(), height, self.volume(height))
+    }
+}
+
+// 使用全部默认实现
+impl Solid for Circle {}
+impl Solid for Rectangle {}
+// endregion: solid
+
+fn associated_types() {
+    println!("--- 关联类型 ---");
+
+    println!("Iterator 用关联类型 Item 指定元素类型，实现 next 后其余方法都有默认实现:");
+    print_region(LESSON.source, "countdown");
+    println!("倒数: {:?}", Countdown { from: 5 }.collect::<Vec<_>>());
+    println!("偶数之和: {}", Countdown { from: 10 }.filter(|n| n % 2 == 0).sum::<u32>());
+    println!();
+
+    println!("关联类型 vs 泛型参数:");
+    println!("- 关联类型: 每个类型只能实现一次 (Countdown 的 Item 只能是 u32)");
+    println!("- 泛型参数: 同一类型可以实现多次 (如 From<i32> 和 From<&str>)");
+}
+
+// region: countdown
+struct Countdown {
+    from: u32,
+}
+
+impl Iterator for Countdown {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.from == 0 {
+            return None;
+        }
+        self.from -= 1;
+        Some(self.from + 1)
+    }
+}
+// endregion: countdown
+
+fn generics_and_bounds() {
+    println!("--- 泛型与约束 ---");
+
+    println!("泛型函数：约束 (bound) 说明类型参数必须实现哪些 trait:");
+    print_region(LESSON.source, "largest");
+    println!("最大的整数: {}", largest(&[3, 41, 7, 12]));
+    println!("最大的字符: {}", largest(&['r', 'u', 's', 't']));
+    println!("所有圆的总面积: {:.2}", total_area(&[Circle { radius: 1.0 }, Circle { radius: 2.0 }]));
+    println!("{}", join_all(&[1.5, 2.5, 3.5], " | "));
+    println!();
+
+    println!("泛型结构体，以及只对满足约束的类型提供的方法:");
+    print_region(LESSON.source, "pair");
+    println!("Pair(3, 8) 中较大的是 {}", Pair::new(3, 8).larger());
+    println!("Pair(\"apple\", \"banana\") 中较大的是 {}", Pair::new("apple", "banana").larger());
+    println!();
+
+    println!("泛型在编译时单态化 (monomorphization)：");
+    println!("largest::<i32> 和 largest::<char> 会生成两份独立的机器码，调用时没有额外开销");
+}
+
+// region: largest
+fn largest<T: PartialOrd + Copy>(items: &[T]) -> T {
+    let mut largest = items[0];
+    for &item in items {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}
+
+fn total_area<S: Shape>(shapes: &[S]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+// 约束较多时写在 where 子句中
+fn join_all<T>(items: &[T], separator: &str) -> String
+where
+    T: fmt::Display,
+{
+    items.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(separator)
+}
+// endregion: largest
+
+// region: pair
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+impl<T> Pair<T> {
+    fn new(first: T, second: T) -> Self {
+        Pair { first, second }
+    }
+}
+
+// 只有 T 可比较且可显示时，Pair<T> 才有 larger 方法
+impl<T: PartialOrd + fmt::Display> Pair<T> {
+    fn larger(&self) -> &T {
+        if self.first >= self.second { &self.first } else { &self.second }
+    }
+}
+// endregion: pair
+
+fn impl_trait() {
+    println!("--- impl Trait ---");
+
+    println!("参数位置的 impl Trait 是泛型的简写，返回位置的 impl Trait 隐藏具体类型:");
+    print_region(LESSON.source, "impl_trait");
+    println!("{}", announce(&Circle { radius: 0.5 }));
+    let add_ten = make_adder(10);
+    println!("make_adder(10)(5) = {}", add_ten(5));
+    println!("20 以内 3 的倍数: {:?}", multiples_of(3, 20).collect::<Vec<_>>());
+    println!();
+
+    println!("闭包和迭代器适配器的类型无法写出，只能用 impl Trait 返回");
+}
+
+// region: impl_trait
+// 等价于 fn announce<S: Shape>(shape: &S) -> String
+fn announce(shape: &impl Shape) -> String {
+    format!("📐 {}: {:.2}", shape.name(), shape.area())
+}
+
+fn make_adder(n: i32) -> impl Fn(i32) -> i32 {
+    move |x| x + n
+}
+
+fn multiples_of(step: u32, limit: u32) -> impl Iterator<Item = u32> {
+    (1..=limit).filter(move |n| n % step == 0)
+}
+// endregion: impl_trait
+
+fn trait_objects() {
+    println!("--- dyn Trait (trait 对象) ---");
+
+    println!("不同类型放进同一个集合时使用 trait 对象，运行时通过虚表 (vtable) 调用方法:");
+    print_region(LESSON.source, "dyn_shapes");
+    let shapes = mixed_shapes();
+    for shape in &shapes {
+        println!("  {}", shape.describe());
+    }
+    println!("总面积: {:.2}", total_area_dyn(&shapes));
+    println!("Box<dyn Shape> 占 {} 字节 (数据指针 + 虚表指针)", size_of::<Box<dyn Shape>>());
+    println!();
+
+    println!("dyn 兼容 (对象安全) 的限制:");
+    println!("- 方法不能有泛型参数");
+    println!("- 方法不能返回 Self");
+    println!("- 不满足时可以给方法加 where Self: Sized，让它不出现在虚表中");
+}
+
+// region: dyn_shapes
+fn mixed_shapes() -> Vec<Box<dyn Shape>> {
+    vec![
+        Box::new(Circle { radius: 1.0 }),
+        Box::new(Rectangle { width: 2.0, height: 5.0 }),
+        Box::new(Circle { radius: 0.5 }),
+    ]
+}
+
+fn total_area_dyn(shapes: &[Box<dyn Shape>]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+// endregion: dyn_shapes
+
+fn standard_traits() {
+    println!("--- 标准库中的常用 trait ---");
+
+    println!("为自己的类型实现标准库 trait，就能使用 {{}} 格式化、.into() 等通用功能:");
+    print_region(LESSON.source, "std_traits");
+    let rectangle: Rectangle = (2.0, 3.5).into();
+    println!("{}", rectangle);
+    println!("{}", Circle::default().describe());
+    println!();
+
+    println!("常用 trait:");
+    println!("- Debug / Display: 调试输出和面向用户的输出");
+    println!("- Clone / Copy: 显式复制和按位复制");
+    println!("- PartialEq / Eq / PartialOrd / Ord: 比较和排序");
+    println!("- From / Into: 类型转换");
+    println!("- Default: 默认值");
+    println!("- Iterator: 迭代");
+    println!("- std::error::Error: 第61课的自定义错误类型就实现了它 (还需要 Debug 和 Display)");
+}
+
+// region: std_traits
+impl fmt::Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "矩形 {} x {}", self.width, self.height)
+    }
+}
+
+// 实现 From 后自动获得对应的 Into
+impl From<(f64, f64)> for Rectangle {
+    fn from((width, height): (f64, f64)) -> Self {
+        Rectangle { width, height }
+    }
+}
+
+impl Default for Circle {
+    fn default() -> Self {
+        Circle { radius: 1.0 }
+    }
+}
+// endregion: std_traits
+
+// 可复制到 main.rs 中运行的示例
+pub fn main_example() {
+    println!("=== 静态分发 vs 动态分发 ===");
+    println!();
+    println!("同样计算 {} 个图形的总面积，比较三种调用方式:", SHAPES);
+    print_region(LESSON.source, "dispatch");
+    println!();
+
+    let circles: Vec<Circle> = (0..SHAPES).map(|i| Circle { radius: (i % 10) as f64 }).collect();
+    let boxed: Vec<Box<dyn Shape>> = (0..SHAPES)
+        .map(|i| Box::new(Circle { radius: (i % 10) as f64 }) as Box<dyn Shape>)
+        .collect();
+    let enums: Vec<AnyShape> = (0..SHAPES).map(|i| AnyShape::Circle(Circle { radius: (i % 10) as f64 })).collect();
+
+    println!("运行结果 ({} 轮):", ROUNDS);
+    let (area, nanos) = bench(|| sum_static(black_box(&circles)));
+    println!("静态分发 (泛型):     总面积 {:.0}，每次调用 {:.2}ns", area, nanos);
+    let (area, nanos) = bench(|| sum_dynamic(black_box(&boxed)));
+    println!("动态分发 (dyn):      总面积 {:.0}，每次调用 {:.2}ns", area, nanos);
+    let (area, nanos) = bench(|| sum_enum(black_box(&enums)));
+    println!("枚举分发 (match):    总面积 {:.0}，每次调用 {:.2}ns", area, nanos);
+    println!();
+
+    println!("说明:");
+    println!("- 静态分发在编译期确定调用目标，可以内联和向量化");
+    println!("- 动态分发每次通过虚表间接调用，无法内联；Box 还让每个元素单独分配在堆上");
+    println!("- 枚举分发用 match 代替虚表，适合类型集合固定的场景");
+    println!("- 调试构建的差距不明显，可以用 cargo run --release learn 43 对比");
+}
+
+const SHAPES: usize = 100_000;
+const ROUNDS: usize = 20;
+
+// 运行 ROUNDS 轮，返回结果和平均每次 area() 调用的耗时 (纳秒)
+fn bench(f: impl Fn() -> f64) -> (f64, f64) {
+    let start = Instant::now();
+    let mut result = 0.0;
+    for _ in 0..ROUNDS {
+        result = black_box(f());
+    }
+    let nanos = start.elapsed().as_nanos() as f64 / (ROUNDS * SHAPES) as f64;
+    (result, nanos)
+}
+
+// region: dispatch
+// 静态分发：编译器为 Circle 生成专门的版本
+fn sum_static<S: Shape>(shapes: &[S]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+// 动态分发：运行时查虚表
+fn sum_dynamic(shapes: &[Box<dyn Shape>]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+// 枚举分发：类型集合固定时用 match 分派
+enum AnyShape {
+    Circle(Circle),
+    Rectangle(Rectangle),
+}
+
+impl Shape for AnyShape {
+    fn name(&self) -> String {
+        match self {
+            AnyShape::Circle(circle) => circle.name(),
+            AnyShape::Rectangle(rectangle) => rectangle.name(),
+        }
+    }
+
+    fn area(&self) -> f64 {
+        match self {
+            AnyShape::Circle(circle) => circle.area(),
+            AnyShape::Rectangle(rectangle) => rectangle.area(),
+        }
+    }
+}
+
+fn sum_enum(shapes: &[AnyShape]) -> f64 {
+    sum_static(shapes)
+}
+// endregion: dispatch
+
+/*
+Trait 与泛型 vs 其他语言：
+
+TypeScript：
+- interface 是结构化类型：形状匹配即可，不需要声明实现
+- 类型只在编译期存在，运行时被擦除
+- 没有默认方法，需要抽象类提供共享实现
+
+Kotlin：
+- interface 可以有默认方法和属性
+- 必须在类定义时声明实现，不能给已有的类补上接口 (扩展函数只能加方法)
+- JVM 泛型会擦除类型参数，接口方法调用都是虚调用
+
+Python：
+- typing.Protocol 是结构化的鸭子类型，只用于静态检查
+- abc.ABC 在运行时检查抽象方法
+- 所有方法调用都在运行时动态查找
+
+Rust：
+- trait 需要显式 impl，可以为已有类型实现自己的 trait (受孤儿规则限制)
+- 泛型在编译期单态化，静态分发没有运行时开销
+- 需要运行时多态时显式写 dyn Trait，开销清楚可见
+- 关联类型、默认方法、supertrait、按约束提供方法 (impl<T: Bound>)
+
+选择建议：
+1. 默认使用泛型 (或 impl Trait)：最快，类型信息完整
+2. 需要把不同类型放进同一集合，或减少编译产物体积时用 dyn Trait
+3. 类型集合固定且不对外扩展时，枚举往往比 dyn Trait 更简单
+*/
@@ -1,6 +1,18 @@
 // 61 - 错误处理
 // Rust 的错误处理哲学：显式、类型安全、零成本
 
+use super::lesson::FnLesson;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "61",
+    title: "错误处理",
+    summary: "Result, ?, panic!, 自定义错误",
+    source: include_str!("_61_error_handling.rs"),
+    run,
+    example: main_example,
+    exercises: &[],
+};
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 61. 错误处理 ===");
@@ -61,18 +73,14 @@ fn recoverable_errors() {
     use std::fs::File;
     use std::io::ErrorKind;
     
-    let filename = "nonexistent.txt";
+    // 一个肯定不存在的路径，演示 NotFound 分支
+    let filename = "/nonexistent/hello.txt";
     
     match File::open(filename) {
         Ok(file) => println!("成功打开文件: {:?}", file),
         Err(error) => match error.kind() {
             ErrorKind::NotFound => {
                 println!("文件 {} 不存在", filename);
-                // 可以尝试创建文件
-                match File::create(filename) {
-                    Ok(_) => println!("创建文件成功"),
-                    Err(e) => println!("创建文件失败: {}", e),
-                }
             },
             other_error => {
                 println!("打开文件时出现其他错误: {:?}", other_error);
@@ -80,16 +88,21 @@ fn recoverable_errors() {
         },
     }
     
-    // 使用 unwrap_or_else 简化错误处理
-    let _file = File::open("another_file.txt").unwrap_or_else(|error| {
+    // 使用 unwrap_or_else 简化错误处理：文件不存在时创建它
+    let path = std::env::temp_dir().join("axum_demo_lesson61.txt");
+    let _file = File::open(&path).unwrap_or_else(|error| {
         if error.kind() == ErrorKind::NotFound {
-            File::create("another_file.txt").unwrap_or_else(|error| {
+            File::create(&path).unwrap_or_else(|error| {
                 panic!("创建文件失败: {:?}", error);
             })
         } else {
             panic!("打开文件失败: {:?}", error);
         }
     });
+    println!("已打开或创建示例文件");
+    
+    // 演示结束后删除示例文件
+    let _ = std::fs::remove_file(&path);
 }
 
 fn question_mark_operator() {
@@ -0,0 +1,486 @@
+// 62 - 并发编程
+// 操作系统线程、消息传递与共享状态：编译器保证没有数据竞争
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use super::lesson::FnLesson;
+use super::listing::print_region;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "62",
+    title: "并发编程",
+    summary: "线程, 通道, Arc<Mutex>, RwLock",
+    source: include_str!("_62_concurrency.rs"),
+    run,
+    example: main_example,
+    exercises: &[],
+};
+
+// 课程输出绑定在当前线程上 (见 output 模块)，
+// 所以示例中的线程只返回结果或发送消息，统一由主线程打印
+
+#[allow(dead_code)]
+pub fn run() {
+    println!("=== 62. 并发编程 ===");
+
+    // 1. 创建线程
+    thread_basics();
+
+    // 2. 消息传递
+    message_passing();
+
+    // 3. 共享状态
+    shared_state();
+
+    // 4. 读写锁
+    read_write_lock();
+
+    // 5. 原子类型
+    atomics();
+
+    // 6. Send 和 Sync
+    send_and_sync();
+}
+
+fn thread_basics() {
+    println!("--- 创建线程 ---");
+
+    println!("thread::spawn 创建操作系统线程，join 等待结束并取回返回值:");
+    print_region(LESSON.source, "spawn");
+    let numbers: Vec<u64> = (1..=1_000_000).collect();
+    println!("4 个线程分段求和: {}", sum_in_threads(&numbers, 4));
+    println!();
+
+    println!("作用域线程 (thread::scope) 可以直接借用栈上的数据:");
+    print_region(LESSON.source, "scope");
+    println!("两个线程分别找最大值: {}", max_in_scope(&[3, 41, 7, 12, 99, 5]));
+    println!();
+
+    println!("多个线程同时等待，总耗时约等于单个任务:");
+    print_region(LESSON.source, "sleepers");
+    let task = Duration::from_millis(50);
+    let start = Instant::now();
+    for _ in 0..4 {
+        thread::sleep(task);
+    }
+    println!("顺序执行 4 个任务，耗时 {}ms", start.elapsed().as_millis());
+    let start = Instant::now();
+    run_in_threads(4, task);
+    println!("4 个线程并发执行，耗时 {}ms", start.elapsed().as_millis());
+}
+
+// region: spawn
+fn sum_in_threads(numbers: &[u64], parts: usize) -> u64 {
+    let chunk_size = numbers.len().div_ceil(parts);
+    let handles: Vec<JoinHandle<u64>> = numbers
+        .chunks(chunk_size)
+        .map(|chunk| {
+            // 线程可能比当前函数活得更久，只能用 move 拿走自己的数据
+            let chunk = chunk.to_vec();
+            thread::spawn(move || chunk.iter().sum())
+        })
+        .collect();
+
+    // 线程 panic 时 join 返回 Err
+    handles.into_iter().map(|handle| handle.join().unwrap()).sum()
+}
+// endregion: spawn
+
+// region: scope
+fn max_in_scope(numbers: &[u64]) -> u64 {
+    let (left, right) = numbers.split_at(numbers.len() / 2);
+
+    // 离开 scope 前所有线程都会被 join，因此可以借用 left 和 right
+    thread::scope(|s| {
+        let left = s.spawn(|| left.iter().copied().max().unwrap_or(0));
+        let right = s.spawn(|| right.iter().copied().max().unwrap_or(0));
+        left.join().unwrap().max(right.join().unwrap())
+    })
+}
+// endregion: scope
+
+// region: sleepers
+fn run_in_threads(count: usize, task: Duration) {
+    let handles: Vec<_> = (0..count).map(|_| thread::spawn(move || thread::sleep(task))).collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+// endregion: sleepers
+
+fn message_passing() {
+    println!("--- 消息传递 (mpsc 通道) ---");
+
+    println!("\"不要通过共享内存来通信，而要通过通信来共享内存\":");
+    print_region(LESSON.source, "channel");
+    let mut messages = collect_messages(3);
+    println!("收到 {} 条消息", messages.len());
+    // 接收顺序取决于线程调度，排序后再打印
+    messages.sort();
+    for message in &messages {
+        println!("  {}", message);
+    }
+    println!();
+
+    println!("有界通道 (sync_channel) 在缓冲区满时阻塞发送方，起到背压作用:");
+    print_region(LESSON.source, "sync_channel");
+    println!("逐个接收: {:?}", bounded_channel());
+}
+
+// region: channel
+fn collect_messages(workers: usize) -> Vec<String> {
+    let (tx, rx) = mpsc::channel();
+
+    for id in 1..=workers {
+        // 每个生产者持有一个发送端的克隆
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let message = format!("工人 {} 完成了 {} 件工作", id, id * 10);
+            tx.send(message).unwrap();
+        });
+    }
+
+    // 丢弃最初的发送端，所有克隆都被丢弃后 rx 的迭代才会结束
+    drop(tx);
+    rx.iter().collect()
+}
+// endregion: channel
+
+// region: sync_channel
+fn bounded_channel() -> Vec<i32> {
+    // 缓冲区只能放 2 条消息
+    let (tx, rx) = mpsc::sync_channel(2);
+
+    let producer = thread::spawn(move || {
+        for i in 1..=5 {
+            tx.send(i).unwrap();
+        }
+    });
+
+    let received = rx.iter().collect();
+    producer.join().unwrap();
+    received
+}
+// endregion: sync_channel
+
+fn shared_state() {
+    println!("--- 共享状态 (Arc<Mutex<T>>) ---");
+
+    println!("Arc 让多个线程共同拥有数据，Mutex 保证同一时刻只有一个线程修改:");
+    print_region(LESSON.source, "mutex");
+    println!("8 个线程各加 1000 次: {}", mutex_counter(8, 1000));
+    println!();
+
+    println!("第88课的服务器也是这样在请求之间共享用户数据:");
+    print_region(LESSON.source, "shared_map");
+    let users = shared_users(&["张三", "李四", "王五"]);
+    println!("用户数: {}，第 1 号用户: {:?}", users.len(), users.get(&1));
+}
+
+// region: mutex
+fn mutex_counter(threads: usize, increments: usize) -> usize {
+    let counter = Arc::new(Mutex::new(0));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..increments {
+                    // lock 返回的守卫离开作用域时自动解锁
+                    *counter.lock().unwrap() += 1;
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    *counter.lock().unwrap()
+}
+// endregion: mutex
+
+// region: shared_map
+fn shared_users(names: &[&str]) -> HashMap<u32, String> {
+    let users = Arc::new(Mutex::new(HashMap::new()));
+
+    thread::scope(|s| {
+        for (id, name) in (1..).zip(names) {
+            let users = Arc::clone(&users);
+            s.spawn(move || {
+                users.lock().unwrap().insert(id, name.to_string());
+            });
+        }
+    });
+
+    // 所有线程都已结束，取回 Mutex 中的数据
+    Arc::try_unwrap(users).unwrap().into_inner().unwrap()
+}
+// endregion: shared_map
+
+fn read_write_lock() {
+    println!("--- 读写锁 (RwLock) ---");
+
+    println!("读多写少时使用 RwLock：多个读者可以同时持有读锁，写锁独占:");
+    print_region(LESSON.source, "rwlock");
+    let hold = Duration::from_millis(50);
+    let start = Instant::now();
+    let readers = readers_with_rwlock(4, hold);
+    println!("RwLock: 4 个读者读到 {:?}，耗时 {}ms", readers, start.elapsed().as_millis());
+    let start = Instant::now();
+    let readers = readers_with_mutex(4, hold);
+    println!("Mutex: 4 个读者读到 {:?}，耗时 {}ms", readers, start.elapsed().as_millis());
+    println!("RwLock 的读者同时持有锁，Mutex 的读者只能排队");
+}
+
+// region: rwlock
+fn readers_with_rwlock(readers: usize, hold: Duration) -> Vec<u32> {
+    let config = Arc::new(RwLock::new(1));
+
+    // 写锁：独占访问
+    *config.write().unwrap() += 1;
+
+    let handles: Vec<_> = (0..readers)
+        .map(|_| {
+            let config = Arc::clone(&config);
+            thread::spawn(move || {
+                let version = config.read().unwrap();
+                // 模拟持有读锁期间的工作
+                thread::sleep(hold);
+                *version
+            })
+        })
+        .collect();
+
+    handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+}
+// endregion: rwlock
+
+// 同样的读者改用 Mutex，用于对比耗时
+fn readers_with_mutex(readers: usize, hold: Duration) -> Vec<u32> {
+    let config = Arc::new(Mutex::new(1));
+    *config.lock().unwrap() += 1;
+
+    let handles: Vec<_> = (0..readers)
+        .map(|_| {
+            let config = Arc::clone(&config);
+            thread::spawn(move || {
+                let version = config.lock().unwrap();
+                thread::sleep(hold);
+                *version
+            })
+        })
+        .collect();
+
+    handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+}
+
+fn atomics() {
+    println!("--- 原子类型 ---");
+
+    println!("简单的计数器不需要锁，原子操作由 CPU 指令保证:");
+    print_region(LESSON.source, "atomic");
+    let start = Instant::now();
+    let count = atomic_counter(8, 10_000);
+    println!("AtomicUsize: {}，耗时 {}ms", count, start.elapsed().as_millis());
+    let start = Instant::now();
+    let count = mutex_counter(8, 10_000);
+    println!("Mutex<usize>: {}，耗时 {}ms", count, start.elapsed().as_millis());
+    println!();
+
+    println!("Ordering 决定原子操作与其他内存访问的先后关系:");
+    println!("- Relaxed: 只保证这个值本身的原子性，适合计数器");
+    println!("- Acquire / Release: 成对使用，发布数据后让其他线程看到");
+    println!("- SeqCst: 所有线程看到同一个全局顺序，最严格也最慢");
+}
+
+// region: atomic
+fn atomic_counter(threads: usize, increments: usize) -> usize {
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..increments {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    counter.load(Ordering::Relaxed)
+}
+// endregion: atomic
+
+fn send_and_sync() {
+    println!("--- Send 和 Sync ---");
+
+    println!("编译器用两个标记 trait 检查跨线程使用:");
+    println!("- Send: 值的所有权可以转移到另一个线程");
+    println!("- Sync: &T 可以在多个线程间共享 (即 &T 是 Send)");
+    println!();
+
+    println!("常见类型:");
+    println!("- i32, String, Vec<T>: Send + Sync");
+    println!("- Rc<T>: 既不是 Send 也不是 Sync (引用计数不是原子操作)");
+    println!("- Cell<T>, RefCell<T>: Send 但不是 Sync");
+    println!("- Arc<T>: T 为 Send + Sync 时才是 Send + Sync");
+    println!("- Mutex<T>: T 为 Send 时就是 Send + Sync");
+    println!("- MutexGuard<T>: 不是 Send，必须在加锁的线程中解锁");
+    println!();
+
+    println!("用泛型约束在编译期检查:");
+    print_region(LESSON.source, "send_sync");
+}
+
+// region: send_sync
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+
+fn check_thread_safety() {
+    assert_send::<Arc<Mutex<Vec<i32>>>>();
+    assert_sync::<Arc<Mutex<Vec<i32>>>>();
+    assert_sync::<AtomicUsize>();
+
+    // 编译错误: `Rc<i32>` cannot be sent between threads safely
+    // let rc = std::rc::Rc::new(1);
+    // thread::spawn(move || println!("{}", rc));
+}
+// endregion: send_sync
+
+// 可复制到 main.rs 中运行的示例
+pub fn main_example() {
+    println!("=== 线程池示例 ===");
+    println!();
+    println!("固定数量的工作线程从同一个通道中取任务，结果通过另一个通道返回:");
+    print_region(LESSON.source, "thread_pool");
+    println!();
+
+    println!("运行结果:");
+    let start = Instant::now();
+    let mut results = square_all(4, 8, Duration::from_millis(30));
+    println!("4 个工作线程处理 8 个任务，耗时 {}ms", start.elapsed().as_millis());
+    results.sort();
+    println!("结果 (排序后): {:?}", results);
+    println!();
+
+    println!("为什么没有数据竞争:");
+    println!("- 任务通过通道转移所有权，同一个任务只会被一个线程拿到");
+    println!("- 多个线程共享的接收端放在 Arc<Mutex<...>> 中");
+    println!("- Job 要求 Send + 'static，编译器拒绝把不能跨线程的数据交给线程池");
+    println!("- ThreadPool 被丢弃时关闭通道并 join 所有线程，不会留下悬空的线程");
+}
+
+// region: thread_pool
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+pub struct ThreadPool {
+    workers: Vec<JoinHandle<()>>,
+    sender: Option<Sender<Job>>,
+}
+
+impl ThreadPool {
+    pub fn new(size: usize) -> ThreadPool {
+        assert!(size > 0);
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..size)
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || worker_loop(&receiver))
+            })
+            .collect();
+
+        ThreadPool { workers, sender: Some(sender) }
+    }
+
+    pub fn execute<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if let Some(sender) = &self.sender {
+            sender.send(Box::new(job)).unwrap();
+        }
+    }
+}
+
+fn worker_loop(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        // 取到任务后立即释放锁，其他工作线程可以继续取任务
+        let job = receiver.lock().unwrap().recv();
+        match job {
+            Ok(job) => job(),
+            // 发送端已关闭，线程退出
+            Err(_) => break,
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // 先关闭通道，再等待所有工作线程结束
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            worker.join().unwrap();
+        }
+    }
+}
+
+fn square_all(workers: usize, jobs: u64, work: Duration) -> Vec<u64> {
+    let pool = ThreadPool::new(workers);
+    let (tx, rx) = mpsc::channel();
+
+    for n in 1..=jobs {
+        let tx = tx.clone();
+        pool.execute(move || {
+            // 模拟耗时的计算
+            thread::sleep(work);
+            tx.send(n * n).unwrap();
+        });
+    }
+
+    drop(tx);
+    rx.iter().collect()
+}
+// endregion: thread_pool
+
+/*
+并发编程 vs 其他语言：
+
+Go：
+- goroutine 是运行时调度的轻量级线程，go f() 即可启动
+- channel 是首选的通信方式，也有 sync.Mutex
+- 数据竞争只能靠 go test -race 在运行时发现
+
+Python：
+- GIL 让同一时刻只有一个线程执行 Python 字节码
+- threading 适合 I/O 密集型任务，CPU 密集型要用 multiprocessing
+- Python 3.13 起可以选择关闭 GIL 的构建
+
+Kotlin：
+- 协程 (coroutine) 由 launch / async 启动，挂起时不占用线程
+- 结构化并发：协程作用域结束前等待所有子协程
+- 共享可变状态仍需要 Mutex 或原子类型，编译器不检查
+
+Rust：
+- std::thread 是操作系统线程，async/await (第84课) 是协作式任务
+- thread::scope 与 Kotlin 的结构化并发类似
+- Send / Sync 在编译期拒绝数据竞争，不需要运行时检测
+- 没有 GIL，多线程可以同时跑满多个 CPU 核心
+
+选择建议：
+1. CPU 密集型任务：线程或线程池 (如 rayon)
+2. 大量 I/O 等待：async/await 和 tokio
+3. 优先用通道传递数据，必须共享时用 Arc<Mutex<T>> 或 Arc<RwLock<T>>
+4. 简单计数和标志位：原子类型
+*/
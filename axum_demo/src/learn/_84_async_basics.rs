@@ -1,12 +1,31 @@
 // 84 - 异步基础
 // Rust 的异步编程模型：零成本异步抽象
 
+use std::time::{Duration, Instant};
+
+use tokio::join;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+use tokio::time::{error::Elapsed, sleep, timeout};
+
+use super::lesson::FnLesson;
+use super::listing::print_region;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "84",
+    title: "异步基础",
+    summary: "async/await, Future, 异步概念",
+    source: include_str!("_84_async_basics.rs"),
+    run,
+    example: main_example,
+    exercises: &[],
+};
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 84. 异步基础 ===");
     
-    // 注意：这些示例展示异步编程概念
-    // 实际运行需要异步运行时如 tokio
+    // 这里讲概念并展示代码片段，可运行的示例在 main_example 中用 tokio 运行时执行
     
     basic_async_concepts();
     future_basics();
@@ -34,10 +53,7 @@ fn future_basics() {
     
     // Future trait 的概念演示
     println!("Future trait 定义（简化版）:");
-    println!("trait Future {{");
-    println!("    type Output;");
-    println!("    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output>;");
-    println!("}}");
+    print_region(LESSON.source, "future_trait");
     println!("");
     
     println!("Future 状态:");
@@ -45,38 +61,94 @@ fn future_basics() {
     println!("- Poll::Ready(value): 已完成，返回结果");
 }
 
+// 标准库 Future 的简化版，放在单独的模块中避免与 std::future::Future 混淆
+mod simplified {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    // region: future_trait
+    trait Future {
+        type Output;
+        fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output>;
+    }
+    // endregion: future_trait
+}
+
 fn async_await_syntax() {
     println!("--- async/await 语法 ---");
     
-    // 演示异步函数语法（实际运行需要异步运行时）
+    // 演示异步函数语法，片段来自本文件中的 region
     println!("异步函数定义:");
-    println!("async fn fetch_data() -> Result<String, Error> {{");
-    println!("    // 异步操作");
-    println!("    Ok(\"data\".to_string())");
-    println!("}}");
+    print_region(LESSON.source, "async_fn");
     println!("");
     
     println!("调用异步函数:");
-    println!("let result = fetch_data().await?;");
+    print_region(LESSON.source, "await_call");
     println!("");
     
     println!("异步块:");
-    println!("let future = async {{");
-    println!("    let data = fetch_data().await?;");
-    println!("    process_data(data).await");
-    println!("}}");
+    print_region(LESSON.source, "async_block");
 }
 
 fn async_error_handling() {
     println!("--- 异步错误处理 ---");
     
     println!("异步函数中的错误处理:");
-    println!("async fn process_request() -> Result<Response, Error> {{");
-    println!("    let data = fetch_data().await?;  // 传播错误");
-    println!("    let validated = validate(data).await?;");
-    println!("    let response = transform(validated).await?;");
-    println!("    Ok(response)");
-    println!("}}");
+    print_region(LESSON.source, "process_request");
+}
+
+// async/await 语法示例：只展示，不运行
+mod syntax {
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    // region: async_fn
+    async fn fetch_data() -> Result<String, Error> {
+        // 异步操作
+        Ok("data".to_string())
+    }
+    // endregion: async_fn
+
+    async fn process_data(data: String) -> Result<usize, Error> {
+        Ok(data.len())
+    }
+
+    async fn call() -> Result<String, Error> {
+        // region: await_call
+        let result = fetch_data().await?;
+        // endregion: await_call
+        Ok(result)
+    }
+
+    fn block() -> impl Future<Output = Result<usize, Error>> {
+        // region: async_block
+        let future = async {
+            let data = fetch_data().await?;
+            process_data(data).await
+        };
+        // endregion: async_block
+        future
+    }
+
+    struct Response {
+        body: String,
+    }
+
+    async fn validate(data: String) -> Result<String, Error> {
+        if data.is_empty() { Err("数据不能为空".into()) } else { Ok(data) }
+    }
+
+    async fn transform(data: String) -> Result<Response, Error> {
+        Ok(Response { body: data })
+    }
+
+    // region: process_request
+    async fn process_request() -> Result<Response, Error> {
+        let data = fetch_data().await?;  // 传播错误
+        let validated = validate(data).await?;
+        let response = transform(validated).await?;
+        Ok(response)
+    }
+    // endregion: process_request
 }
 
 // 可复制到 main.rs 中运行的示例（需要添加 tokio 依赖）
@@ -85,192 +157,299 @@ pub fn main_example() {
     println!();
     println!("要运行异步代码，需要在 Cargo.toml 中添加:");
     println!("[dependencies]");
-    println!("tokio = {{ version = \"1.0\", features = [\"full\"] }}");
+    println!("tokio = {{ version = \"1\", features = [\"full\"] }}");
     println!();
     println!("然后将 main 函数改为:");
-    println!("#[tokio::main]");
-    println!("async fn main() {{");
-    println!("    // 异步代码");
-    println!("}}");
+    print_region(LESSON.source, "tokio_main");
     println!();
     
-    // 演示异步编程模式
-    show_async_patterns();
+    // 下面的模式都在真实的 tokio 运行时上执行；单线程运行时让任务中的打印
+    // 也留在当前线程，和课程的其他输出在一起
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .expect("无法创建 tokio 运行时");
+    runtime.block_on(show_async_patterns());
+    
+    show_complete_example();
+}
+
+mod tokio_main {
+    // region: tokio_main
+    #[tokio::main]
+    async fn main() {
+        // 异步代码
+    }
+    // endregion: tokio_main
 }
 
-fn show_async_patterns() {
+async fn show_async_patterns() {
     println!("--- 常见异步模式 ---");
     
     println!("1. 简单异步函数:");
-    println!("async fn greet(name: &str) -> String {{");
-    println!("    format!(\"Hello, {{}}\", name)");
-    println!("}}");
+    print_region(LESSON.source, "greet");
+    println!("运行结果: {}", greet("Rust").await);
     println!();
     
-    println!("2. 异步 I/O 操作:");
-    println!("use tokio::fs::File;");
-    println!("use tokio::io::AsyncReadExt;");
-    println!();
-    println!("async fn read_file(path: &str) -> Result<String, std::io::Error> {{");
-    println!("    let mut file = File::open(path).await?;");
-    println!("    let mut contents = String::new();");
-    println!("    file.read_to_string(&mut contents).await?;");
-    println!("    Ok(contents)");
-    println!("}}");
-    println!();
-    
-    println!("3. 并发执行多个任务:");
-    println!("use tokio::join;");
-    println!();
-    println!("async fn fetch_multiple() -> Result<(String, String), Error> {{");
-    println!("    let (result1, result2) = join!(");
-    println!("        fetch_data_from_api1(),");
-    println!("        fetch_data_from_api2()");
-    println!("    );");
-    println!("    Ok((result1?, result2?))");
-    println!("}}");
+    println!("2. 并发执行多个任务 (join! vs 顺序 await):");
+    print_region(LESSON.source, "join");
+    let start = Instant::now();
+    let (first, second) = fetch_sequential().await;
+    println!("顺序执行: {}, {}，耗时 {}ms", first, second, start.elapsed().as_millis());
+    let start = Instant::now();
+    let (first, second) = fetch_concurrent().await;
+    println!("join! 并发: {}, {}，耗时 {}ms", first, second, start.elapsed().as_millis());
     println!();
     
-    println!("4. 超时处理:");
-    println!("use tokio::time::{{timeout, Duration}};");
-    println!();
-    println!("async fn fetch_with_timeout() -> Result<String, Error> {{");
-    println!("    let result = timeout(");
-    println!("        Duration::from_secs(5),");
-    println!("        fetch_data()");
-    println!("    ).await??;");
-    println!("    Ok(result)");
-    println!("}}");
+    println!("3. 超时处理:");
+    print_region(LESSON.source, "timeout");
+    let limit = Duration::from_millis(100);
+    match fetch_with_timeout(20, limit).await {
+        Ok(data) => println!("期限内完成: {}", data),
+        Err(e) => println!("超时: {}", e),
+    }
+    match fetch_with_timeout(500, limit).await {
+        Ok(data) => println!("期限内完成: {}", data),
+        Err(e) => println!("超时: {}", e),
+    }
     println!();
     
-    println!("5. 异步迭代器:");
-    println!("use tokio_stream::StreamExt;");
-    println!();
-    println!("async fn process_stream() {{");
-    println!("    let mut stream = create_data_stream();");
-    println!("    while let Some(item) = stream.next().await {{");
-    println!("        process_item(item).await;");
-    println!("    }}");
-    println!("}}");
+    println!("4. 流式处理 (边接收边处理):");
+    print_region(LESSON.source, "stream");
+    let total = process_stream().await;
+    println!("处理完毕，合计 {}", total);
     println!();
     
-    println!("6. 异步闭包（future版本）:");
-    println!("let tasks: Vec<_> = urls.into_iter()");
-    println!("    .map(|url| async move {{");
-    println!("        fetch_url(&url).await");
-    println!("    }})");
-    println!("    .collect();");
-    println!();
-    println!("let results = futures::future::join_all(tasks).await;");
+    println!("5. 动态数量的并发任务:");
+    print_region(LESSON.source, "join_set");
+    let start = Instant::now();
+    // 延迟相差足够大，机器繁忙时完成顺序也不会变
+    let results = fetch_all(&[("users", 300), ("orders", 50), ("stock", 150)]).await;
+    println!("按完成顺序: {:?}，耗时 {}ms", results, start.elapsed().as_millis());
     println!();
     
-    println!("7. 错误处理和重试:");
-    println!("async fn retry_operation<F, T, E>(");
-    println!("    mut operation: F,");
-    println!("    max_retries: usize");
-    println!(") -> Result<T, E>");
-    println!("where");
-    println!("    F: FnMut() -> futures::future::BoxFuture<'static, Result<T, E>>,");
-    println!("{{");
-    println!("    for attempt in 0..max_retries {{");
-    println!("        match operation().await {{");
-    println!("            Ok(result) => return Ok(result),");
-    println!("            Err(e) if attempt == max_retries - 1 => return Err(e),");
-    println!("            Err(_) => {{");
-    println!("                tokio::time::sleep(Duration::from_millis(100 * (attempt + 1) as u64)).await;");
-    println!("            }}");
-    println!("        }}");
-    println!("    }}");
-    println!("    unreachable!()");
-    println!("}}");
+    println!("6. 错误处理和重试 (指数退避):");
+    print_region(LESSON.source, "retry");
+    let mut calls = 0;
+    let result = retry_operation(
+        || {
+            calls += 1;
+            let call = calls;
+            async move {
+                if call < 3 {
+                    Err(format!("第{}次连接被拒绝", call))
+                } else {
+                    Ok(call)
+                }
+            }
+        },
+        5,
+    )
+    .await;
+    println!("重试结果: {:?} (第 {} 次调用成功)", result, calls);
+    let result: Result<(), &str> = retry_operation(|| async { Err("服务不可用") }, 3).await;
+    println!("重试结果: {:?}", result);
     println!();
-    
-    show_complete_example();
 }
 
+// region: greet
+async fn greet(name: &str) -> String {
+    format!("Hello, {}", name)
+}
+// endregion: greet
+
+// region: join
+// 模拟一次耗时的网络请求，返回数据来源
+async fn fetch_data(source: &str, millis: u64) -> String {
+    sleep(Duration::from_millis(millis)).await;
+    source.to_string()
+}
+
+// 依次 await：总耗时是两次请求之和
+async fn fetch_sequential() -> (String, String) {
+    let first = fetch_data("api1", 100).await;
+    let second = fetch_data("api2", 100).await;
+    (first, second)
+}
+
+// join! 同时等待两个 Future：总耗时约等于较慢的一次
+async fn fetch_concurrent() -> (String, String) {
+    join!(fetch_data("api1", 100), fetch_data("api2", 100))
+}
+// endregion: join
+
+// region: timeout
+// 超过期限时返回 Err(Elapsed)，未完成的 Future 直接被丢弃
+async fn fetch_with_timeout(millis: u64, limit: Duration) -> Result<String, Elapsed> {
+    timeout(limit, fetch_data("slow-api", millis)).await
+}
+// endregion: timeout
+
+// region: stream
+// 生产者隔一段时间发送一条数据，消费者收到一条处理一条，不必等全部到齐
+async fn process_stream() -> u32 {
+    let (tx, mut rx) = mpsc::channel(4);
+    tokio::spawn(async move {
+        for item in 1..=5 {
+            sleep(Duration::from_millis(20)).await;
+            if tx.send(item).await.is_err() {
+                break;
+            }
+        }
+        // tx 在这里被丢弃，接收端随后收到 None
+    });
+
+    let start = Instant::now();
+    let mut total = 0;
+    while let Some(item) = rx.recv().await {
+        println!("  收到第 {} 条数据 ({}ms)", item, start.elapsed().as_millis());
+        total += item;
+    }
+    total
+}
+// endregion: stream
+
+// region: join_set
+// 任务数量在运行时才确定时用 JoinSet，结果按完成顺序返回
+async fn fetch_all(jobs: &[(&'static str, u64)]) -> Vec<String> {
+    let mut set = JoinSet::new();
+    for &(source, millis) in jobs {
+        set.spawn(fetch_data(source, millis));
+    }
+
+    let mut results = Vec::new();
+    while let Some(result) = set.join_next().await {
+        results.push(result.expect("任务 panic"));
+    }
+    results
+}
+// endregion: join_set
+
+// region: retry
+// 失败后等待一段时间再试，每次等待时间翻倍，达到次数上限后返回最后一次的错误
+async fn retry_operation<F, Fut, T, E>(mut operation: F, max_attempts: u32) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    let mut delay = Duration::from_millis(10);
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= max_attempts => return Err(e),
+            Err(e) => {
+                println!("  第{}次尝试失败: {}，{}ms 后重试", attempt, e, delay.as_millis());
+                sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
+// endregion: retry
+
 fn show_complete_example() {
-    println!("--- 完整的异步 Web 服务示例 ---");
+    println!("--- 完整的异步示例 ---");
     println!();
     println!("// Cargo.toml");
     println!("[dependencies]");
-    println!("tokio = {{ version = \"1.0\", features = [\"full\"] }}");
-    println!("reqwest = {{ version = \"0.11\", features = [\"json\"] }}");
-    println!("serde = {{ version = \"1.0\", features = [\"derive\"] }}");
-    println!("serde_json = \"1.0\"");
+    println!("tokio = {{ version = \"1\", features = [\"full\"] }}");
     println!();
     println!("// main.rs");
-    println!("use reqwest::Error;");
-    println!("use serde::{{Deserialize, Serialize}};");
-    println!("use std::collections::HashMap;");
-    println!("use tokio::time::{{sleep, Duration}};");
-    println!();
-    println!("#[derive(Debug, Deserialize, Serialize)]");
-    println!("struct User {{");
-    println!("    id: u32,");
-    println!("    name: String,");
-    println!("    email: String,");
-    println!("}}");
-    println!();
-    println!("async fn fetch_user(id: u32) -> Result<User, Error> {{");
-    println!("    let url = format!(\"https://jsonplaceholder.typicode.com/users/{{}}\", id);");
-    println!("    let user = reqwest::get(&url).await?.json::<User>().await?;");
-    println!("    Ok(user)");
-    println!("}}");
+    print_region(LESSON.source, "complete");
     println!();
-    println!("async fn fetch_multiple_users(ids: Vec<u32>) -> Vec<Result<User, Error>> {{");
-    println!("    let futures = ids.into_iter().map(|id| async move {{");
-    println!("        fetch_user(id).await");
-    println!("    }});");
-    println!("    ");
-    println!("    futures::future::join_all(futures).await");
-    println!("}}");
-    println!();
-    println!("async fn simulate_database_operation(data: &str) -> Result<String, &'static str> {{");
-    println!("    // 模拟数据库延迟");
-    println!("    sleep(Duration::from_millis(100)).await;");
-    println!("    ");
-    println!("    if data.is_empty() {{");
-    println!("        Err(\"数据不能为空\")");
-    println!("    }} else {{");
-    println!("        Ok(format!(\"已保存: {{}}\", data))");
-    println!("    }}");
-    println!("}}");
-    println!();
-    println!("#[tokio::main]");
-    println!("async fn main() -> Result<(), Box<dyn std::error::Error>> {{");
-    println!("    println!(\"开始异步操作演示\");");
-    println!("    ");
-    println!("    // 1. 单个异步操作");
-    println!("    let user = fetch_user(1).await?;");
-    println!("    println!(\"获取到用户: {{:?}}\", user);");
-    println!("    ");
-    println!("    // 2. 并发操作");
-    println!("    let user_ids = vec![1, 2, 3, 4, 5];");
-    println!("    let results = fetch_multiple_users(user_ids).await;");
-    println!("    ");
-    println!("    for (i, result) in results.into_iter().enumerate() {{");
-    println!("        match result {{");
-    println!("            Ok(user) => println!(\"用户 {{}}: {{}}\", i + 1, user.name),");
-    println!("            Err(e) => println!(\"获取用户 {{}} 失败: {{}}\", i + 1, e),");
-    println!("        }}");
-    println!("    }}");
-    println!("    ");
-    println!("    // 3. 错误处理");
-    println!("    match simulate_database_operation(\"test data\").await {{");
-    println!("        Ok(result) => println!(\"数据库操作成功: {{}}\", result),");
-    println!("        Err(e) => println!(\"数据库操作失败: {{}}\", e),");
-    println!("    }}");
-    println!("    ");
-    println!("    Ok(())");
-    println!("}}");
+    
+    println!("运行输出:");
+    complete::run();
     println!();
     
     println!("这个示例展示了:");
-    println!("- HTTP 客户端请求");
-    println!("- 并发执行多个操作");
+    println!("- 用 sleep 模拟网络和数据库延迟 (实际项目中换成 reqwest、sqlx 等异步库)");
+    println!("- tokio::spawn 并发执行多个请求");
     println!("- 异步错误处理");
-    println!("- 模拟数据库操作");
-    println!("- 真实的异步运行时使用");
+    println!("- #[tokio::main] 创建运行时");
+}
+
+// 完整示例：打印的是 region 中的代码，课程中通过 run() 真正运行一次
+mod complete {
+    // region: complete
+    use std::time::Duration;
+
+    use tokio::time::sleep;
+
+    #[derive(Debug, Clone)]
+    struct User {
+        id: u32,
+        name: String,
+    }
+
+    // 模拟用户服务，id 为 1-100 的用户存在
+    async fn fetch_user(id: u32) -> Result<User, String> {
+        sleep(Duration::from_millis(50)).await;
+        match id {
+            1..=100 => Ok(User {
+                id,
+                name: format!("user{}", id),
+            }),
+            _ => Err(format!("用户 {} 不存在", id)),
+        }
+    }
+
+    // 每个请求作为独立任务并发执行，按提交顺序收集结果
+    async fn fetch_multiple_users(ids: Vec<u32>) -> Vec<Result<User, String>> {
+        let handles: Vec<_> = ids.into_iter().map(|id| tokio::spawn(fetch_user(id))).collect();
+        let mut results = Vec::new();
+        for handle in handles {
+            results.push(handle.await.expect("任务 panic"));
+        }
+        results
+    }
+
+    async fn simulate_database_operation(data: &str) -> Result<String, &'static str> {
+        // 模拟数据库延迟
+        sleep(Duration::from_millis(100)).await;
+
+        if data.is_empty() {
+            Err("数据不能为空")
+        } else {
+            Ok(format!("已保存: {}", data))
+        }
+    }
+
+    #[tokio::main]
+    async fn main() {
+        println!("开始异步操作演示");
+
+        // 1. 单个异步操作
+        match fetch_user(1).await {
+            Ok(user) => println!("获取到用户: {:?}", user),
+            Err(e) => println!("获取用户失败: {}", e),
+        }
+
+        // 2. 并发操作，总耗时约等于单个请求
+        let results = fetch_multiple_users(vec![1, 2, 3, 0, 101]).await;
+        for result in results {
+            match result {
+                Ok(user) => println!("用户 {}: {}", user.id, user.name),
+                Err(e) => println!("获取用户失败: {}", e),
+            }
+        }
+
+        // 3. 错误处理
+        for data in ["test data", ""] {
+            match simulate_database_operation(data).await {
+                Ok(result) => println!("数据库操作成功: {}", result),
+                Err(e) => println!("数据库操作失败: {}", e),
+            }
+        }
+    }
+    // endregion: complete
+
+    pub fn run() {
+        main()
+    }
 }
 
 /*
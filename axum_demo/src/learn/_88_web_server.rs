@@ -1,6 +1,19 @@
 // 88 - Web 服务器
 // 使用 Axum 构建现代 Web 服务器
 
+use super::lesson::FnLesson;
+use super::listing::print_region;
+
+pub const LESSON: FnLesson = FnLesson {
+    id: "88",
+    title: "Web 服务器",
+    summary: "Axum 框架, HTTP 服务",
+    source: include_str!("_88_web_server.rs"),
+    run,
+    example: main_example,
+    exercises: &[],
+};
+
 #[allow(dead_code)]
 pub fn run() {
     println!("=== 88. Web 服务器 ===");
@@ -40,325 +53,254 @@ fn axum_basics() {
     println!();
     println!("// Cargo.toml 依赖");
     println!("[dependencies]");
-    println!("axum = \"0.7\"");
-    println!("tokio = {{ version = \"1.0\", features = [\"full\"] }}");
-    println!("tower = \"0.4\"");
-    println!("serde = {{ version = \"1.0\", features = [\"derive\"] }}");
-    println!("serde_json = \"1.0\"");
+    println!("axum = \"0.8\"");
+    println!("tokio = {{ version = \"1\", features = [\"full\"] }}");
+    println!("serde = {{ version = \"1\", features = [\"derive\"] }}");
     println!();
     
     println!("// 基本服务器");
-    println!("use axum::{{");
-    println!("    extract::{{Path, Query}},");
-    println!("    http::StatusCode,");
-    println!("    response::{{Html, Json}},");
-    println!("    routing::{{get, post}},");
-    println!("    Router,");
-    println!("}};");
-    println!("use std::collections::HashMap;");
-    println!();
-    
-    println!("async fn hello() -> &'static str {{");
-    println!("    \"Hello, World!\"");
-    println!("}}");
-    println!();
-    
-    println!("#[tokio::main]");
-    println!("async fn main() {{");
-    println!("    let app = Router::new()");
-    println!("        .route(\"/\", get(hello));");
-    println!();
-    println!("    let listener = tokio::net::TcpListener::bind(\"127.0.0.1:3000\")");
-    println!("        .await.unwrap();");
-    println!("    ");
-    println!("    println!(\"服务器启动在 http://127.0.0.1:3000\");");
-    println!("    axum::serve(listener, app).await.unwrap();");
-    println!("}}");
+    print_region(LESSON.source, "basic");
+}
+
+// 各小节的示例代码放在各自的模块中，互不影响，只编译不运行
+mod basic {
+    // region: basic
+    use axum::{routing::get, Router};
+
+    async fn hello() -> &'static str {
+        "Hello, World!"
+    }
+
+    #[tokio::main]
+    async fn main() {
+        let app = Router::new().route("/", get(hello));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await.unwrap();
+
+        println!("服务器启动在 http://127.0.0.1:3000");
+        axum::serve(listener, app).await.unwrap();
+    }
+    // endregion: basic
 }
 
 fn routing_examples() {
     println!("--- 路由示例 ---");
     
-    println!("// 路径参数");
-    println!("async fn get_user(Path(user_id): Path<u32>) -> String {{");
-    println!("    format!(\"用户 ID: {{}}\", user_id)");
-    println!("}}");
-    println!();
-    
-    println!("// 查询参数");
-    println!("async fn search(Query(params): Query<HashMap<String, String>>) -> String {{");
-    println!("    format!(\"搜索参数: {{:?}}\", params)");
-    println!("}}");
-    println!();
-    
-    println!("// JSON 请求体");
-    println!("#[derive(serde::Deserialize)]");
-    println!("struct CreateUser {{");
-    println!("    name: String,");
-    println!("    email: String,");
-    println!("}}");
-    println!();
-    println!("async fn create_user(Json(payload): Json<CreateUser>) -> Json<CreateUser> {{");
-    println!("    Json(payload)");
-    println!("}}");
-    println!();
-    
-    println!("// 路由组合");
-    println!("let app = Router::new()");
-    println!("    .route(\"/\", get(hello))");
-    println!("    .route(\"/users/:id\", get(get_user))");
-    println!("    .route(\"/search\", get(search))");
-    println!("    .route(\"/users\", post(create_user))");
-    println!("    .nest(\"/api/v1\", api_routes())");
-    println!("    .nest_service(\"/static\", tower_http::services::ServeDir::new(\"static\"));");
+    print_region(LESSON.source, "routing");
+}
+
+mod routing {
+    // region: routing
+    use std::collections::HashMap;
+
+    use axum::{
+        extract::{Path, Query},
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde::{Deserialize, Serialize};
+    use tower_http::services::ServeDir;
+
+    // 路径参数
+    async fn get_user(Path(user_id): Path<u32>) -> String {
+        format!("用户 ID: {}", user_id)
+    }
+
+    // 查询参数
+    async fn search(Query(params): Query<HashMap<String, String>>) -> String {
+        format!("搜索参数: {:?}", params)
+    }
+
+    // JSON 请求体
+    #[derive(Deserialize, Serialize)]
+    struct CreateUser {
+        name: String,
+        email: String,
+    }
+
+    async fn create_user(Json(payload): Json<CreateUser>) -> Json<CreateUser> {
+        Json(payload)
+    }
+
+    // 路由组合
+    fn app() -> Router {
+        Router::new()
+            .route("/", get(|| async { "Hello, World!" }))
+            .route("/users/{id}", get(get_user))
+            .route("/search", get(search))
+            .route("/users", post(create_user))
+            .nest("/api/v1", api_routes())
+            .nest_service("/static", ServeDir::new("static"))
+    }
+    // endregion: routing
+
+    fn api_routes() -> Router {
+        Router::new().route("/ping", get(|| async { "pong" }))
+    }
 }
 
 fn middleware_examples() {
     println!("--- 中间件示例 ---");
     
     println!("// 日志中间件");
-    println!("use tower_http::trace::TraceLayer;");
-    println!();
-    println!("let app = Router::new()");
-    println!("    .route(\"/\", get(hello))");
-    println!("    .layer(TraceLayer::new_for_http());");
+    print_region(LESSON.source, "trace");
     println!();
     
     println!("// CORS 中间件");
-    println!("use tower_http::cors::{{CorsLayer, Origin}};");
-    println!();
-    println!("let cors = CorsLayer::new()");
-    println!("    .allow_origin(Origin::exact(\"http://localhost:3000\".parse().unwrap()))");
-    println!("    .allow_methods([Method::GET, Method::POST])");
-    println!("    .allow_headers([CONTENT_TYPE]);");
-    println!();
-    
-    println!("let app = Router::new()");
-    println!("    .route(\"/api/data\", get(get_data))");
-    println!("    .layer(cors);");
+    print_region(LESSON.source, "cors");
     println!();
     
     println!("// 自定义中间件");
-    println!("use axum::middleware;");
-    println!();
-    println!("async fn auth_middleware<B>(");
-    println!("    request: Request<B>,");
-    println!("    next: Next<B>,");
-    println!(") -> Result<Response, StatusCode> {{");
-    println!("    let auth_header = request.headers()");
-    println!("        .get(\"authorization\")");
-    println!("        .and_then(|header| header.to_str().ok());");
-    println!();
-    println!("    match auth_header {{");
-    println!("        Some(token) if token.starts_with(\"Bearer \") => {{");
-    println!("            Ok(next.run(request).await)");
-    println!("        }}");
-    println!("        _ => Err(StatusCode::UNAUTHORIZED),");
-    println!("    }}");
-    println!("}}");
-    println!();
-    
-    println!("let protected_routes = Router::new()");
-    println!("    .route(\"/profile\", get(get_profile))");
-    println!("    .layer(middleware::from_fn(auth_middleware));");
+    print_region(LESSON.source, "auth_middleware");
+}
+
+mod middleware_layers {
+    use axum::{routing::get, Router};
+
+    async fn hello() -> &'static str {
+        "Hello, World!"
+    }
+
+    async fn get_data() -> &'static str {
+        "data"
+    }
+
+    async fn get_profile() -> &'static str {
+        "profile"
+    }
+
+    // region: trace
+    use tower_http::trace::TraceLayer;
+
+    fn traced() -> Router {
+        Router::new()
+            .route("/", get(hello))
+            .layer(TraceLayer::new_for_http())
+    }
+    // endregion: trace
+
+    // region: cors
+    use axum::http::{header::CONTENT_TYPE, HeaderValue, Method};
+    use tower_http::cors::CorsLayer;
+
+    fn with_cors() -> Router {
+        let cors = CorsLayer::new()
+            .allow_origin("http://localhost:3000".parse::<HeaderValue>().unwrap())
+            .allow_methods([Method::GET, Method::POST])
+            .allow_headers([CONTENT_TYPE]);
+
+        Router::new()
+            .route("/api/data", get(get_data))
+            .layer(cors)
+    }
+    // endregion: cors
+
+    // region: auth_middleware
+    // axum 0.8 中 Request 和 Next 不再带请求体的泛型参数
+    use axum::{
+        extract::Request,
+        http::StatusCode,
+        middleware::{self, Next},
+        response::Response,
+    };
+
+    async fn auth_middleware(request: Request, next: Next) -> Result<Response, StatusCode> {
+        let auth_header = request
+            .headers()
+            .get("authorization")
+            .and_then(|header| header.to_str().ok());
+
+        match auth_header {
+            Some(token) if token.starts_with("Bearer ") => Ok(next.run(request).await),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+
+    fn protected_routes() -> Router {
+        Router::new()
+            .route("/profile", get(get_profile))
+            .layer(middleware::from_fn(auth_middleware))
+    }
+    // endregion: auth_middleware
 }
 
 fn error_handling_web() {
     println!("--- Web 错误处理 ---");
     
-    println!("// 自定义错误类型");
-    println!("#[derive(Debug)]");
-    println!("enum AppError {{");
-    println!("    NotFound,");
-    println!("    BadRequest(String),");
-    println!("    InternalServerError,");
-    println!("}}");
-    println!();
-    
-    println!("impl IntoResponse for AppError {{");
-    println!("    fn into_response(self) -> Response {{");
-    println!("        let (status, error_message) = match self {{");
-    println!("            AppError::NotFound => (StatusCode::NOT_FOUND, \"未找到资源\"),");
-    println!("            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.as_str()),");
-    println!("            AppError::InternalServerError => (");
-    println!("                StatusCode::INTERNAL_SERVER_ERROR,");
-    println!("                \"内部服务器错误\"");
-    println!("            ),");
-    println!("        }};");
-    println!();
-    println!("        let body = Json(json!({{");
-    println!("            \"error\": error_message");
-    println!("        }}));");
-    println!();
-    println!("        (status, body).into_response()");
-    println!("    }}");
-    println!("}}");
-    println!();
-    
-    println!("// 使用自定义错误");
-    println!("async fn get_user_by_id(Path(id): Path<u32>) -> Result<Json<User>, AppError> {{");
-    println!("    if id == 0 {{");
-    println!("        return Err(AppError::BadRequest(\"ID 不能为 0\".to_string()));");
-    println!("    }}");
-    println!();
-    println!("    // 模拟数据库查询");
-    println!("    if id > 1000 {{");
-    println!("        return Err(AppError::NotFound);");
-    println!("    }}");
-    println!();
-    println!("    Ok(Json(User {{");
-    println!("        id,");
-    println!("        name: format!(\"用户{{}}\", id),");
-    println!("        email: format!(\"user{{}}@example.com\", id),");
-    println!("    }}))");
-    println!("}}");
+    print_region(LESSON.source, "app_error");
+}
+
+mod app_error {
+    // region: app_error
+    use axum::{
+        extract::Path,
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        Json,
+    };
+    use serde::Serialize;
+    use serde_json::json;
+
+    // 自定义错误类型
+    #[derive(Debug)]
+    enum AppError {
+        NotFound,
+        BadRequest(String),
+        InternalServerError,
+    }
+
+    impl IntoResponse for AppError {
+        fn into_response(self) -> Response {
+            let (status, error_message) = match self {
+                AppError::NotFound => (StatusCode::NOT_FOUND, "未找到资源".to_string()),
+                AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+                AppError::InternalServerError => {
+                    (StatusCode::INTERNAL_SERVER_ERROR, "内部服务器错误".to_string())
+                }
+            };
+
+            let body = Json(json!({
+                "error": error_message
+            }));
+
+            (status, body).into_response()
+        }
+    }
+
+    #[derive(Serialize)]
+    struct User {
+        id: u32,
+        name: String,
+        email: String,
+    }
+
+    // 使用自定义错误
+    async fn get_user_by_id(Path(id): Path<u32>) -> Result<Json<User>, AppError> {
+        if id == 0 {
+            return Err(AppError::BadRequest("ID 不能为 0".to_string()));
+        }
+
+        // 模拟数据库查询
+        if id > 1000 {
+            return Err(AppError::NotFound);
+        }
+
+        Ok(Json(User {
+            id,
+            name: format!("用户{}", id),
+            email: format!("user{}@example.com", id),
+        }))
+    }
+    // endregion: app_error
 }
 
 fn complete_server_example() {
     println!("--- 完整服务器示例 ---");
     
-    println!("这是一个完整的 Axum Web 服务器示例:");
+    println!("这是一个完整的 Axum Web 服务器示例 (运行 cargo run learn 88 --serve 启动):");
     println!();
     println!("// src/main.rs");
-    println!("use axum::{{");
-    println!("    extract::{{Path, Query, State}},");
-    println!("    http::StatusCode,");
-    println!("    middleware,");
-    println!("    response::{{Html, IntoResponse, Json}},");
-    println!("    routing::{{get, post, put, delete}},");
-    println!("    Router,");
-    println!("}};");
-    println!("use serde::{{Deserialize, Serialize}};");
-    println!("use std::{{");
-    println!("    collections::HashMap,");
-    println!("    sync::{{Arc, Mutex}},");
-    println!("}};");
-    println!("use tower_http::{{cors::CorsLayer, trace::TraceLayer}};");
-    println!();
-    
-    println!("// 数据模型");
-    println!("#[derive(Debug, Clone, Serialize, Deserialize)]");
-    println!("struct User {{");
-    println!("    id: u32,");
-    println!("    name: String,");
-    println!("    email: String,");
-    println!("}}");
-    println!();
-    
-    println!("#[derive(Deserialize)]");
-    println!("struct CreateUserRequest {{");
-    println!("    name: String,");
-    println!("    email: String,");
-    println!("}}");
-    println!();
-    
-    println!("// 应用状态");
-    println!("type UserStore = Arc<Mutex<HashMap<u32, User>>>;");
-    println!();
-    
-    println!("#[derive(Clone)]");
-    println!("struct AppState {{");
-    println!("    users: UserStore,");
-    println!("    next_id: Arc<Mutex<u32>>,");
-    println!("}}");
-    println!();
-    
-    println!("// 路由处理器");
-    println!("async fn health_check() -> &'static str {{");
-    println!("    \"服务器运行正常\"");
-    println!("}}");
-    println!();
-    
-    println!("async fn get_users(State(state): State<AppState>) -> Json<Vec<User>> {{");
-    println!("    let users = state.users.lock().unwrap();");
-    println!("    let user_list: Vec<User> = users.values().cloned().collect();");
-    println!("    Json(user_list)");
-    println!("}}");
-    println!();
-    
-    println!("async fn get_user(");
-    println!("    Path(id): Path<u32>,");
-    println!("    State(state): State<AppState>,");
-    println!(") -> Result<Json<User>, StatusCode> {{");
-    println!("    let users = state.users.lock().unwrap();");
-    println!("    match users.get(&id) {{");
-    println!("        Some(user) => Ok(Json(user.clone())),");
-    println!("        None => Err(StatusCode::NOT_FOUND),");
-    println!("    }}");
-    println!("}}");
-    println!();
-    
-    println!("async fn create_user(");
-    println!("    State(state): State<AppState>,");
-    println!("    Json(request): Json<CreateUserRequest>,");
-    println!(") -> Result<Json<User>, StatusCode> {{");
-    println!("    let mut next_id = state.next_id.lock().unwrap();");
-    println!("    let id = *next_id;");
-    println!("    *next_id += 1;");
-    println!("    drop(next_id);");
-    println!();
-    println!("    let user = User {{");
-    println!("        id,");
-    println!("        name: request.name,");
-    println!("        email: request.email,");
-    println!("    }};");
-    println!();
-    println!("    let mut users = state.users.lock().unwrap();");
-    println!("    users.insert(id, user.clone());");
-    println!();
-    println!("    Ok(Json(user))");
-    println!("}}");
-    println!();
-    
-    println!("async fn delete_user(");
-    println!("    Path(id): Path<u32>,");
-    println!("    State(state): State<AppState>,");
-    println!(") -> StatusCode {{");
-    println!("    let mut users = state.users.lock().unwrap();");
-    println!("    match users.remove(&id) {{");
-    println!("        Some(_) => StatusCode::NO_CONTENT,");
-    println!("        None => StatusCode::NOT_FOUND,");
-    println!("    }}");
-    println!("}}");
-    println!();
-    
-    println!("#[tokio::main]");
-    println!("async fn main() {{");
-    println!("    // 初始化日志");
-    println!("    tracing_subscriber::init();");
-    println!();
-    println!("    // 创建应用状态");
-    println!("    let state = AppState {{");
-    println!("        users: Arc::new(Mutex::new(HashMap::new())),");
-    println!("        next_id: Arc::new(Mutex::new(1)),");
-    println!("    }};");
-    println!();
-    println!("    // 构建路由");
-    println!("    let app = Router::new()");
-    println!("        .route(\"/\", get(|| async {{ \"欢迎使用 Rust Web API!\" }}))");
-    println!("        .route(\"/health\", get(health_check))");
-    println!("        .route(\"/users\", get(get_users).post(create_user))");
-    println!("        .route(\"/users/:id\", get(get_user).delete(delete_user))");
-    println!("        .with_state(state)");
-    println!("        .layer(CorsLayer::permissive())");
-    println!("        .layer(TraceLayer::new_for_http());");
-    println!();
-    println!("    // 启动服务器");
-    println!("    let listener = tokio::net::TcpListener::bind(\"127.0.0.1:3000\")");
-    println!("        .await");
-    println!("        .unwrap();");
-    println!();
-    println!("    println!(\"🚀 服务器启动在 http://127.0.0.1:3000\");");
-    println!("    println!(\"📋 可用端点:\");");
-    println!("    println!(\"  GET  /           - 欢迎页面\");");
-    println!("    println!(\"  GET  /health     - 健康检查\");");
-    println!("    println!(\"  GET  /users      - 获取所有用户\");");
-    println!("    println!(\"  POST /users      - 创建用户\");");
-    println!("    println!(\"  GET  /users/:id  - 获取指定用户\");");
-    println!("    println!(\"  DELETE /users/:id - 删除用户\");");
-    println!();
-    println!("    axum::serve(listener, app).await.unwrap();");
-    println!("}}");
+    print_region(LESSON.source, "server");
     println!();
     
     println!("API 使用示例:");
@@ -377,6 +319,143 @@ fn complete_server_example() {
     println!("curl -X DELETE http://localhost:3000/users/1");
 }
 
+// 完整服务器示例：上面打印的就是这段真实编译的代码，learn 88 --serve 运行其中的 main()
+// region: server
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use tower_http::{cors::CorsLayer, trace::TraceLayer};
+
+// 数据模型
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct User {
+    id: u32,
+    name: String,
+    email: String,
+}
+
+#[derive(Deserialize)]
+struct CreateUserRequest {
+    name: String,
+    email: String,
+}
+
+// 应用状态
+type UserStore = Arc<Mutex<HashMap<u32, User>>>;
+
+#[derive(Clone)]
+struct AppState {
+    users: UserStore,
+    next_id: Arc<Mutex<u32>>,
+}
+
+// 路由处理器
+async fn health_check() -> &'static str {
+    "服务器运行正常"
+}
+
+async fn get_users(State(state): State<AppState>) -> Json<Vec<User>> {
+    let users = state.users.lock().unwrap();
+    let mut user_list: Vec<User> = users.values().cloned().collect();
+    user_list.sort_by_key(|user| user.id);
+    Json(user_list)
+}
+
+async fn get_user(
+    Path(id): Path<u32>,
+    State(state): State<AppState>,
+) -> Result<Json<User>, StatusCode> {
+    let users = state.users.lock().unwrap();
+    match users.get(&id) {
+        Some(user) => Ok(Json(user.clone())),
+        None => Err(StatusCode::NOT_FOUND),
+    }
+}
+
+async fn create_user(
+    State(state): State<AppState>,
+    Json(request): Json<CreateUserRequest>,
+) -> (StatusCode, Json<User>) {
+    let mut next_id = state.next_id.lock().unwrap();
+    let id = *next_id;
+    *next_id += 1;
+    drop(next_id);
+
+    let user = User {
+        id,
+        name: request.name,
+        email: request.email,
+    };
+
+    let mut users = state.users.lock().unwrap();
+    users.insert(id, user.clone());
+
+    (StatusCode::CREATED, Json(user))
+}
+
+async fn delete_user(
+    Path(id): Path<u32>,
+    State(state): State<AppState>,
+) -> StatusCode {
+    let mut users = state.users.lock().unwrap();
+    match users.remove(&id) {
+        Some(_) => StatusCode::NO_CONTENT,
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    // 初始化日志，TraceLayer 会记录每个请求
+    tracing_subscriber::fmt()
+        .with_env_filter("tower_http=debug")
+        .init();
+
+    // 创建应用状态
+    let state = AppState {
+        users: Arc::new(Mutex::new(HashMap::new())),
+        next_id: Arc::new(Mutex::new(1)),
+    };
+
+    // 构建路由，axum 0.8 的路径参数写作 {id}
+    let app = Router::new()
+        .route("/", get(|| async { "欢迎使用 Rust Web API!" }))
+        .route("/health", get(health_check))
+        .route("/users", get(get_users).post(create_user))
+        .route("/users/{id}", get(get_user).delete(delete_user))
+        .with_state(state)
+        .layer(CorsLayer::permissive())
+        .layer(TraceLayer::new_for_http());
+
+    // 启动服务器，端口默认 3000，可用 PORT 环境变量修改
+    let port = std::env::var("PORT").unwrap_or_else(|_| "3000".to_string());
+    let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port)).await?;
+
+    println!("🚀 服务器启动在 http://{}", listener.local_addr()?);
+    println!("📋 可用端点:");
+    println!("  GET    /            - 欢迎页面");
+    println!("  GET    /health      - 健康检查");
+    println!("  GET    /users       - 获取所有用户");
+    println!("  POST   /users       - 创建用户");
+    println!("  GET    /users/{{id}}  - 获取指定用户");
+    println!("  DELETE /users/{{id}}  - 删除用户");
+
+    axum::serve(listener, app).await
+}
+// endregion: server
+
+// learn 88 --serve
+pub fn serve() -> std::io::Result<()> {
+    main()
+}
+
 // 可复制到 main.rs 中运行的示例
 pub fn main_example() {
     println!("=== Web 服务器快速开始 ===");
@@ -385,19 +464,17 @@ pub fn main_example() {
     println!();
     println!("1. 在 Cargo.toml 中添加依赖:");
     println!("[dependencies]");
-    println!("axum = \"0.7\"");
-    println!("tokio = {{ version = \"1.0\", features = [\"full\"] }}");
-    println!("serde = {{ version = \"1.0\", features = [\"derive\"] }}");
-    println!("serde_json = \"1.0\"");
-    println!("tower = \"0.4\"");
-    println!("tower-http = {{ version = \"0.5\", features = [\"full\"] }}");
-    println!("tracing = \"0.1\"");
-    println!("tracing-subscriber = \"0.3\"");
+    println!("axum = \"0.8\"");
+    println!("tokio = {{ version = \"1\", features = [\"full\"] }}");
+    println!("serde = {{ version = \"1\", features = [\"derive\"] }}");
+    println!("tower-http = {{ version = \"0.6\", features = [\"cors\", \"trace\"] }}");
+    println!("tracing-subscriber = {{ version = \"0.3\", features = [\"env-filter\"] }}");
     println!();
     println!("2. 将上面的完整示例代码复制到 src/main.rs");
     println!();
     println!("3. 运行服务器:");
     println!("cargo run");
+    println!("(在本项目中可以直接运行 cargo run learn 88 --serve)");
     println!();
     println!("4. 测试 API:");
     println!("curl http://localhost:3000/health");
@@ -409,61 +486,20 @@ pub fn main_example() {
 fn show_advanced_features() {
     println!("--- 高级特性 ---");
     
-    println!("1. WebSocket 支持:");
-    println!("use axum::{{");
-    println!("    extract::{{ws::{{Message, WebSocket, WebSocketUpgrade}}}},");
-    println!("    response::Response,");
-    println!("}};");
-    println!();
-    println!("async fn websocket_handler(ws: WebSocketUpgrade) -> Response {{");
-    println!("    ws.on_upgrade(handle_socket)");
-    println!("}}");
+    println!("1. WebSocket 支持 (axum 需启用 ws feature):");
+    print_region(LESSON.source, "websocket");
     println!();
     
-    println!("2. 文件上传:");
-    println!("use axum::extract::Multipart;");
-    println!();
-    println!("async fn upload(mut multipart: Multipart) {{");
-    println!("    while let Some(field) = multipart.next_field().await.unwrap() {{");
-    println!("        let name = field.name().unwrap().to_string();");
-    println!("        let data = field.bytes().await.unwrap();");
-    println!("        println!(\"上传文件: {{}}, 大小: {{}}\", name, data.len());");
-    println!("    }}");
-    println!("}}");
+    println!("2. 文件上传 (axum 需启用 multipart feature):");
+    print_region(LESSON.source, "upload");
     println!();
     
     println!("3. 数据库集成 (SQLx):");
-    println!("use sqlx::{{PgPool, Row}};");
-    println!();
-    println!("async fn get_users_from_db(");
-    println!("    State(pool): State<PgPool>");
-    println!(") -> Result<Json<Vec<User>>, StatusCode> {{");
-    println!("    let users = sqlx::query!(\"SELECT id, name, email FROM users\")");
-    println!("        .fetch_all(&pool)");
-    println!("        .await");
-    println!("        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;");
-    println!("    ");
-    println!("    // 转换为 User 结构体...");
-    println!("    Ok(Json(vec![]))  // 简化示例");
-    println!("}}");
+    print_region(LESSON.source, "database");
     println!();
     
     println!("4. JWT 认证:");
-    println!("use jsonwebtoken::{{decode, DecodingKey, Validation}};");
-    println!();
-    println!("async fn verify_jwt(");
-    println!("    TypedHeader(authorization): TypedHeader<Authorization<Bearer>>,");
-    println!(") -> Result<Json<Claims>, StatusCode> {{");
-    println!("    let token = authorization.token();");
-    println!("    let claims = decode::<Claims>(");
-    println!("        token,");
-    println!("        &DecodingKey::from_secret(\"secret\".as_ref()),");
-    println!("        &Validation::default(),");
-    println!("    )");
-    println!("    .map_err(|_| StatusCode::UNAUTHORIZED)?;");
-    println!("    ");
-    println!("    Ok(Json(claims.claims))");
-    println!("}}");
+    print_region(LESSON.source, "jwt");
     println!();
     
     println!("Axum 的优势:");
@@ -475,6 +511,103 @@ fn show_advanced_features() {
     println!("✅ 与 tokio 生态系统完美集成");
 }
 
+mod websocket {
+    // region: websocket
+    use axum::{
+        extract::ws::{Message, WebSocket, WebSocketUpgrade},
+        response::Response,
+    };
+
+    async fn websocket_handler(ws: WebSocketUpgrade) -> Response {
+        ws.on_upgrade(handle_socket)
+    }
+
+    // 把收到的文本消息原样发回
+    async fn handle_socket(mut socket: WebSocket) {
+        while let Some(Ok(message)) = socket.recv().await {
+            if let Message::Text(text) = message
+                && socket.send(Message::Text(text)).await.is_err()
+            {
+                break;
+            }
+        }
+    }
+    // endregion: websocket
+}
+
+mod upload {
+    // region: upload
+    use axum::extract::Multipart;
+
+    async fn upload(mut multipart: Multipart) {
+        while let Some(field) = multipart.next_field().await.unwrap() {
+            let name = field.name().unwrap().to_string();
+            let data = field.bytes().await.unwrap();
+            println!("上传文件: {}, 大小: {}", name, data.len());
+        }
+    }
+    // endregion: upload
+}
+
+mod database {
+    // region: database
+    use axum::{extract::State, http::StatusCode, Json};
+    use serde::Serialize;
+    use sqlx::PgPool;
+
+    #[derive(Serialize, sqlx::FromRow)]
+    struct User {
+        id: i32,
+        name: String,
+        email: String,
+    }
+
+    // query_as 在运行时映射到 User；query! 宏则在编译时连接数据库检查 SQL
+    async fn get_users_from_db(State(pool): State<PgPool>) -> Result<Json<Vec<User>>, StatusCode> {
+        let users = sqlx::query_as::<_, User>("SELECT id, name, email FROM users")
+            .fetch_all(&pool)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        Ok(Json(users))
+    }
+    // endregion: database
+}
+
+mod jwt {
+    // region: jwt
+    use axum::{
+        http::{HeaderMap, StatusCode},
+        Json,
+    };
+    use jsonwebtoken::{decode, DecodingKey, Validation};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Claims {
+        sub: String,
+        exp: usize,
+    }
+
+    // 从 Authorization: Bearer <token> 请求头中取出令牌并校验
+    async fn verify_jwt(headers: HeaderMap) -> Result<Json<Claims>, StatusCode> {
+        let token = headers
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+        let claims = decode::<Claims>(
+            token,
+            &DecodingKey::from_secret("secret".as_ref()),
+            &Validation::default(),
+        )
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+        Ok(Json(claims.claims))
+    }
+    // endregion: jwt
+}
+
 /*
 Web 框架对比：
 
@@ -0,0 +1,220 @@
+// 课程手册 (learn export)
+// 把全部课程渲染为静态手册，可直接放到内部文档站点：
+//   index.md / index.html  - 目录，按学习阶段列出课程
+//   01.md / 01.html ...    - 每课一页：课程输出 (与 learn <编号> --format markdown/html 相同)
+//                            加上源码末尾的语言对比说明
+//   style.css              - 仅 HTML
+//
+// 页面文字跟随 --lang，导出不记录学习进度
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::lesson::{Lesson, Stage};
+use super::output::{self, escape_html};
+use super::LESSONS;
+use crate::i18n::{self, tr, Lang};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    // Markdown，适合 mdBook 或代码托管平台直接浏览
+    Md,
+    // 单独的 HTML 页面，附带 style.css
+    Html,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Md => "md",
+            Format::Html => "html",
+        }
+    }
+
+    fn output(self) -> output::Format {
+        match self {
+            Format::Md => output::Format::Markdown,
+            Format::Html => output::Format::Html,
+        }
+    }
+}
+
+const STYLE: &str = "\
+body { max-width: 52rem; margin: 2rem auto; padding: 0 1rem; font-family: system-ui, sans-serif; line-height: 1.6; color: #222; }
+nav { margin: 1rem 0; color: #666; }
+a { color: #b7410e; text-decoration: none; }
+a:hover { text-decoration: underline; }
+h1 { border-bottom: 2px solid #b7410e; padding-bottom: .3rem; }
+pre { background: #f6f6f6; border-radius: 4px; padding: .8rem 1rem; overflow-x: auto; }
+li { margin: .2rem 0; }
+pre.rust .kw { color: #a626a4; }
+pre.rust .ty { color: #0184bc; }
+pre.rust .ma { color: #4078f2; }
+pre.rust .st { color: #50a14f; }
+pre.rust .nu, pre.rust .lt { color: #986801; }
+pre.rust .co { color: #a0a1a7; font-style: italic; }
+pre.rust .at { color: #888; }
+";
+
+// 写入整本手册，返回导出的课程数
+pub fn export(format: Format, dir: &Path) -> io::Result<usize> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(file_name("index", format)), index(format))?;
+    for (index, lesson) in LESSONS.iter().enumerate() {
+        let previous = index.checked_sub(1).map(|index| LESSONS[index]);
+        let next = LESSONS.get(index + 1).copied();
+        fs::write(dir.join(file_name(lesson.id(), format)), page(format, *lesson, previous, next))?;
+    }
+    if format == Format::Html {
+        fs::write(dir.join("style.css"), STYLE)?;
+    }
+    Ok(LESSONS.len())
+}
+
+fn file_name(name: &str, format: Format) -> String {
+    format!("{}.{}", name, format.extension())
+}
+
+fn book_title() -> &'static str {
+    i18n::lookup("Rust 学习手册")
+}
+
+// 目录页
+fn index(format: Format) -> String {
+    let mut page = Page::new(format);
+    page.heading(1, book_title());
+    page.paragraph(&page.escape(&tr!("共 {} 课，按学习阶段排列。", LESSONS.len())));
+    for stage in Stage::ALL {
+        let lessons: Vec<&dyn Lesson> = LESSONS.iter().copied().filter(|lesson| lesson.stage() == stage).collect();
+        if lessons.is_empty() {
+            continue;
+        }
+        page.heading(2, &format!("{} {}", stage.icon(), stage));
+        let items: Vec<String> = lessons
+            .iter()
+            .map(|lesson| format!("{} - {}", page.lesson_link(*lesson, ""), page.escape(lesson.summary())))
+            .collect();
+        page.list(&items);
+    }
+    page.finish(book_title())
+}
+
+// 课程页：导航 + 课程输出 + 语言对比 + 导航
+fn page(format: Format, lesson: &dyn Lesson, previous: Option<&dyn Lesson>, next: Option<&dyn Lesson>) -> String {
+    let mut page = Page::new(format);
+    let mut links = vec![page.link(i18n::lookup("目录"), &file_name("index", format))];
+    links.extend(previous.map(|lesson| page.lesson_link(lesson, "← ")));
+    links.extend(next.map(|lesson| format!("{} →", page.lesson_link(lesson, ""))));
+    let nav = links.join(" · ");
+
+    page.nav(&nav);
+    page.raw(&output::capture(format.output(), || super::run(lesson)));
+    if let Some(notes) = lesson.notes() {
+        page.heading(2, i18n::lookup("语言对比"));
+        page.block(notes);
+    }
+    page.nav(&nav);
+
+    let title = tr!("第{}课：{}", lesson.id(), lesson.title());
+    page.finish(&format!("{} - {}", title, book_title()))
+}
+
+// 按格式拼接页面，Markdown 与 HTML 的结构相同
+struct Page {
+    format: Format,
+    body: String,
+}
+
+impl Page {
+    fn new(format: Format) -> Self {
+        Page { format, body: String::new() }
+    }
+
+    fn escape(&self, text: &str) -> String {
+        match self.format {
+            Format::Md => text.to_string(),
+            Format::Html => escape_html(text),
+        }
+    }
+
+    fn link(&self, text: &str, target: &str) -> String {
+        match self.format {
+            Format::Md => format!("[{}]({})", text, target),
+            Format::Html => format!("<a href=\"{}\">{}</a>", target, escape_html(text)),
+        }
+    }
+
+    fn lesson_link(&self, lesson: &dyn Lesson, prefix: &str) -> String {
+        let text = format!("{}{}. {}", prefix, lesson.id(), lesson.title());
+        self.link(&text, &file_name(lesson.id(), self.format))
+    }
+
+    fn heading(&mut self, level: usize, text: &str) {
+        let heading = match self.format {
+            Format::Md => format!("{} {}\n\n", "#".repeat(level), text),
+            Format::Html => format!("<h{0}>{1}</h{0}>\n", level, escape_html(text)),
+        };
+        self.body.push_str(&heading);
+    }
+
+    // markup 已按格式转义
+    fn paragraph(&mut self, markup: &str) {
+        let paragraph = match self.format {
+            Format::Md => format!("{}\n\n", markup),
+            Format::Html => format!("<p>{}</p>\n", markup),
+        };
+        self.body.push_str(&paragraph);
+    }
+
+    fn nav(&mut self, markup: &str) {
+        match self.format {
+            Format::Md => self.paragraph(markup),
+            Format::Html => self.body.push_str(&format!("<nav>{}</nav>\n", markup)),
+        }
+    }
+
+    fn list(&mut self, items: &[String]) {
+        let list = match self.format {
+            Format::Md => items.iter().map(|item| format!("- {}\n", item)).collect::<String>() + "\n",
+            Format::Html => {
+                let items: String = items.iter().map(|item| format!("<li>{}</li>\n", item)).collect();
+                format!("<ul>\n{}</ul>\n", items)
+            }
+        };
+        self.body.push_str(&list);
+    }
+
+    fn block(&mut self, text: &str) {
+        let block = match self.format {
+            Format::Md => format!("```text\n{}\n```\n\n", text),
+            Format::Html => format!("<pre>{}</pre>\n", escape_html(text)),
+        };
+        self.body.push_str(&block);
+    }
+
+    fn raw(&mut self, text: &str) {
+        self.body.push_str(text);
+    }
+
+    fn finish(self, title: &str) -> String {
+        match self.format {
+            Format::Md => self.body,
+            Format::Html => {
+                let lang = match i18n::current() {
+                    Lang::Zh => "zh-CN",
+                    Lang::En => "en",
+                };
+                format!(
+                    "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n\
+                     <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+                     <title>{}</title>\n<link rel=\"stylesheet\" href=\"style.css\">\n</head>\n\
+                     <body>\n{}</body>\n</html>\n",
+                    lang,
+                    escape_html(title),
+                    self.body
+                )
+            }
+        }
+    }
+}
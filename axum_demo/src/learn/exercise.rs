@@ -0,0 +1,313 @@
+// 课程练习 (learn exercise 23)
+// 每课可以声明两类练习:
+//   Choice - 选择题，在终端中作答
+//   Fix    - 修改代码，与 rustlings 类似：第一次运行时把代码复制到用户数据目录，
+//            修改后再次运行，程序把它放进临时 crate 用本机 cargo 编译，
+//            并附上初始代码中的测试运行 (用户删除或忽略自己文件中的测试不影响评判)
+// 结果记录在学习进度中
+
+use std::io::{self, BufRead, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::progress::{self, Progress};
+use super::Lesson;
+use crate::i18n::tr;
+
+pub enum Exercise {
+    Choice(Choice),
+    Fix(Fix),
+}
+
+pub struct Choice {
+    pub question: &'static str,
+    pub options: &'static [&'static str],
+    // 正确选项的下标，从 0 开始
+    pub answer: usize,
+    pub explanation: &'static str,
+}
+
+pub struct Fix {
+    // 用户数据目录下的文件名，如 21_ownership.rs
+    pub file: &'static str,
+    pub prompt: &'static str,
+    // 初始代码，位于仓库 exercises/ 目录，以 #[cfg(test)] mod tests 结尾，评判时使用其中的测试
+    pub code: &'static str,
+    pub hint: &'static str,
+}
+
+// 编译失败时最多显示的错误行数
+const MAX_ERROR_LINES: usize = 30;
+
+// 依次完成本课的练习，返回是否全部通过
+pub fn run(lesson: &dyn Lesson, reset: bool) -> io::Result<bool> {
+    let exercises = lesson.exercises();
+    let mut progress = Progress::load()?;
+    let mut passed = 0;
+
+    println!("🦀 第{}课练习：{} ({} 题)", lesson.id(), lesson.title(), exercises.len());
+    for (index, exercise) in exercises.iter().enumerate() {
+        println!();
+        println!("--- 第 {} 题 ---", index + 1);
+        let ok = match exercise {
+            Exercise::Choice(choice) => ask(choice)?,
+            Exercise::Fix(fix) => check(fix, reset)?,
+        };
+        progress.record_exercise(&key(lesson, index), ok);
+        progress.save()?;
+        if ok {
+            passed += 1;
+        }
+    }
+
+    println!();
+    println!("结果: {}/{} 通过", passed, exercises.len());
+    Ok(passed == exercises.len())
+}
+
+// 进度中的练习编号，如 23.2
+pub fn key(lesson: &dyn Lesson, index: usize) -> String {
+    format!("{}.{}", lesson.id(), index + 1)
+}
+
+fn ask(choice: &Choice) -> io::Result<bool> {
+    println!("{}", choice.question);
+    for (index, option) in choice.options.iter().enumerate() {
+        println!("  {}. {}", letter(index), option);
+    }
+    print!("你的答案: ");
+    io::Write::flush(&mut io::stdout())?;
+
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    // 答案来自管道时补上换行，保持输出整齐
+    if !io::stdin().is_terminal() {
+        println!("{}", line.trim());
+    }
+    let picked = parse_answer(line.trim(), choice.options.len());
+
+    let ok = picked == Some(choice.answer);
+    if ok {
+        println!("✅ 正确");
+    } else {
+        println!("❌ 正确答案是 {}", letter(choice.answer));
+    }
+    println!("{}", choice.explanation);
+    Ok(ok)
+}
+
+fn letter(index: usize) -> char {
+    (b'A' + index as u8) as char
+}
+
+// 接受字母 (A/b) 或从 1 开始的数字
+fn parse_answer(input: &str, count: usize) -> Option<usize> {
+    let index = match input.parse::<usize>() {
+        Ok(number) => number.checked_sub(1)?,
+        Err(_) => {
+            let c = input.to_ascii_uppercase().chars().next()?;
+            (c as usize).checked_sub('A' as usize)?
+        }
+    };
+    (index < count).then_some(index)
+}
+
+fn check(fix: &Fix, reset: bool) -> io::Result<bool> {
+    let path = progress::data_dir()?.join("exercises").join(fix.file);
+    if reset || !path.exists() {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, fix.code)?;
+    }
+
+    println!("{}", fix.prompt);
+    println!("📄 {}", path.display());
+
+    let (ok, errors) = compile_and_test(&path, fix)?;
+    if ok {
+        println!("✅ 编译通过，测试全部通过");
+    } else {
+        for line in errors.lines().take(MAX_ERROR_LINES) {
+            println!("  {}", line);
+        }
+        println!("❌ 还没有通过，修改上面的文件后重新运行");
+        println!("💡 提示: {}", fix.hint);
+    }
+    Ok(ok)
+}
+
+// 初始代码中 #[cfg(test)] mod tests { ... } 的内容
+fn shipped_tests(code: &str) -> Option<&str> {
+    let start = code.find("#[cfg(test)]\nmod tests {")?;
+    let body = &code[start..];
+    Some(&body[body.find('{')? + 1..body.rfind('}')?])
+}
+
+// 在临时 crate 中运行 cargo test，返回 (是否通过, 失败时的输出)
+// 初始代码中的测试以 grading 模块附在用户代码之后，只运行这些测试，并要求全部运行且通过
+fn compile_and_test(source: &Path, fix: &Fix) -> io::Result<(bool, String)> {
+    let name = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "exercise".to_string());
+    let root = std::env::temp_dir().join("axum_demo-exercises");
+    let dir = root.join(&name);
+    std::fs::create_dir_all(dir.join("src"))?;
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"exercise_{}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[workspace]\n",
+            name
+        ),
+    )?;
+    let tests = shipped_tests(fix.code).unwrap_or_default();
+    let code = std::fs::read_to_string(source)?;
+    std::fs::write(dir.join("src/lib.rs"), format!("{}\n\n#[cfg(test)]\nmod grading {{{}}}\n", code, tests))?;
+    let expected = tests.matches("#[test]").count();
+
+    // 用户通过 cargo run 运行时 CARGO 指向当前工具链
+    let cargo = std::env::var_os("CARGO").map_or_else(|| PathBuf::from("cargo"), PathBuf::from);
+    let output = Command::new(cargo)
+        .args(["test", "--quiet", "--lib", "--", "--include-ignored", "grading::"])
+        .current_dir(&dir)
+        // 所有练习共用编译缓存
+        .env("CARGO_TARGET_DIR", root.join("target"))
+        .output()
+        .map_err(|e| io::Error::new(e.kind(), tr!("无法运行 cargo: {}", e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.success() {
+        let passed = passed_tests(&stdout);
+        if expected > 0 && passed == expected {
+            return Ok((true, String::new()));
+        }
+        return Ok((false, tr!("应通过 {} 个练习测试，实际通过 {} 个", expected, passed)));
+    }
+    let mut text = String::from_utf8_lossy(&output.stderr).into_owned();
+    text.push_str(&stdout);
+    Ok((false, text.trim().to_string()))
+}
+
+// libtest 汇总行中通过的测试数: test result: ok. 1 passed; 0 failed; ...
+fn passed_tests(output: &str) -> usize {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix("test result: ok. "))
+        .filter_map(|rest| rest.split(' ').next()?.parse::<usize>().ok())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::learn::LESSONS;
+
+    fn fixes() -> impl Iterator<Item = &'static Fix> {
+        LESSONS
+            .iter()
+            .flat_map(|lesson| lesson.exercises())
+            .filter_map(|exercise| match exercise {
+                Exercise::Fix(fix) => Some(fix),
+                Exercise::Choice(_) => None,
+            })
+    }
+
+    #[test]
+    fn parses_letters_and_numbers() {
+        assert_eq!(parse_answer("A", 4), Some(0));
+        assert_eq!(parse_answer("c", 4), Some(2));
+        assert_eq!(parse_answer("1", 4), Some(0));
+        assert_eq!(parse_answer("4", 4), Some(3));
+        // 只看第一个字母
+        assert_eq!(parse_answer("b. 借用", 4), Some(1));
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        for input in ["", "?", "-1", "中"] {
+            assert_eq!(parse_answer(input, 4), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_answers() {
+        for input in ["0", "5", "E", "z", "99999999999999999999"] {
+            assert_eq!(parse_answer(input, 4), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn choice_answers_are_valid_options() {
+        for lesson in LESSONS {
+            for (index, exercise) in lesson.exercises().iter().enumerate() {
+                if let Exercise::Choice(choice) = exercise {
+                    let key = key(*lesson, index);
+                    assert!(choice.answer < choice.options.len(), "练习 {} 的答案不在选项中", key);
+                }
+            }
+        }
+    }
+
+    // 没有修改的初始代码必须不能通过，否则什么都不改也算完成
+    #[test]
+    fn shipped_fix_starters_fail() {
+        let dir = std::env::temp_dir().join("axum_demo-exercise-tests");
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut count = 0;
+        for fix in fixes() {
+            let path = dir.join(fix.file);
+            std::fs::write(&path, fix.code).unwrap();
+            let (ok, errors) = compile_and_test(&path, fix).unwrap();
+            assert!(!ok, "{} 的初始代码直接通过了测试", fix.file);
+            assert!(!errors.is_empty(), "{} 失败但没有输出", fix.file);
+            count += 1;
+        }
+        assert!(count > 0, "没有修改代码类的练习");
+    }
+
+    fn fix(file: &str) -> &'static Fix {
+        fixes().find(|fix| fix.file == file).unwrap()
+    }
+
+    // 以初始代码为基础改写后评判，文件名区分各个测试，避免并行时互相覆盖
+    fn grade(name: &str, fix: &Fix, code: &str) -> bool {
+        let dir = std::env::temp_dir().join("axum_demo-exercise-tests");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{}.rs", name));
+        std::fs::write(&path, code).unwrap();
+        compile_and_test(&path, fix).unwrap().0
+    }
+
+    #[test]
+    fn every_fix_ships_tests() {
+        for fix in fixes() {
+            let tests = shipped_tests(fix.code).unwrap_or_else(|| panic!("{} 没有 mod tests", fix.file));
+            assert!(tests.contains("#[test]"), "{} 没有测试", fix.file);
+        }
+    }
+
+    #[test]
+    fn parses_passed_test_count() {
+        assert_eq!(passed_tests("\nrunning 2 tests\n..\ntest result: ok. 2 passed; 0 failed; 0 ignored;\n"), 2);
+        assert_eq!(passed_tests("running 0 tests\n\ntest result: ok. 0 passed; 0 failed;\n"), 0);
+    }
+
+    // 删掉或忽略自己文件中的测试不能绕过评判
+    #[test]
+    fn removing_or_ignoring_tests_does_not_pass() {
+        let fix = fix("42_enums.rs");
+        let wrong = fix.code.replace("// TODO: 处理 Triangle", "Shape::Triangle { .. } => 0.0,");
+        let removed = &wrong[..wrong.find("#[cfg(test)]").unwrap()];
+        assert!(!grade("42_removed", fix, removed));
+        assert!(!grade("42_ignored", fix, &wrong.replace("#[test]", "#[test]\n    #[ignore]")));
+    }
+
+    #[test]
+    fn solution_without_its_own_tests_passes() {
+        let fix = fix("42_enums.rs");
+        let solved =
+            fix.code.replace("// TODO: 处理 Triangle", "Shape::Triangle { base, height } => base * height / 2.0,");
+        assert!(grade("42_solved", fix, &solved[..solved.find("#[cfg(test)]").unwrap()]));
+    }
+}
@@ -0,0 +1,149 @@
+// 课程接口与阶段划分
+// 命令行、课程列表、补全和 README 清单都从 learn::LESSONS 读取，
+// 新增课程只需要一个文件加上 mod.rs 中的一行登记
+
+use std::fmt;
+
+use super::exercise::Exercise;
+use crate::i18n::{self, Lang};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Basics,
+    Ownership,
+    DataStructures,
+    Advanced,
+    Practical,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 5] = [
+        Stage::Basics,
+        Stage::Ownership,
+        Stage::DataStructures,
+        Stage::Advanced,
+        Stage::Practical,
+    ];
+
+    // 课程编号决定所属阶段，如 21 属于所有权系统
+    pub fn of(id: &str) -> Stage {
+        let number: u8 = id.parse().unwrap_or(0);
+        Stage::ALL
+            .into_iter()
+            .find(|stage| stage.range().contains(&number))
+            .unwrap_or(Stage::Practical)
+    }
+
+    pub fn range(self) -> std::ops::RangeInclusive<u8> {
+        match self {
+            Stage::Basics => 1..=20,
+            Stage::Ownership => 21..=40,
+            Stage::DataStructures => 41..=60,
+            Stage::Advanced => 61..=80,
+            Stage::Practical => 81..=99,
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            Stage::Basics => "📚",
+            Stage::Ownership => "🔐",
+            Stage::DataStructures => "🏗️",
+            Stage::Advanced => "⚡",
+            Stage::Practical => "🌐",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        i18n::lookup(match self {
+            Stage::Basics => "阶段一：基础语法",
+            Stage::Ownership => "阶段二：所有权系统",
+            Stage::DataStructures => "阶段三：数据结构",
+            Stage::Advanced => "阶段四：高级特性",
+            Stage::Practical => "阶段五：实用开发",
+        })
+    }
+}
+
+// 如 "阶段一：基础语法 (01-20)"
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let range = self.range();
+        write!(f, "{} ({:02}-{:02})", self.name(), range.start(), range.end())
+    }
+}
+
+pub trait Lesson: Sync {
+    // 两位编号，如 "01"
+    fn id(&self) -> &'static str;
+    fn title(&self) -> &'static str;
+    // 一行要点，显示在课程列表中
+    fn summary(&self) -> &'static str;
+    // 课程模块的源码
+    fn source(&self) -> &'static str;
+    // 概念讲解
+    fn run(&self);
+    // 可复制到 main.rs 中运行的示例
+    fn example(&self);
+
+    // 课后练习，可以为空
+    fn exercises(&self) -> &'static [Exercise];
+
+    fn stage(&self) -> Stage {
+        Stage::of(self.id())
+    }
+
+    // 源码末尾 /* 与其他语言对比 ... */ 注释中的内容，英文见 locales/en.toml 的 [notes]
+    fn notes(&self) -> Option<&'static str> {
+        if i18n::current() == Lang::En
+            && let Some(note) = i18n::note(self.id())
+        {
+            return Some(note);
+        }
+        let source = self.source();
+        let start = source.rfind("\n/*")? + 3;
+        let end = start + source[start..].find("*/")?;
+        Some(source[start..end].trim())
+    }
+}
+
+// 由模块中的 run() / main_example() 组成的课程，现有课程都使用这种形式
+pub struct FnLesson {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub summary: &'static str,
+    pub source: &'static str,
+    pub run: fn(),
+    pub example: fn(),
+    pub exercises: &'static [Exercise],
+}
+
+impl Lesson for FnLesson {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn title(&self) -> &'static str {
+        i18n::lookup(self.title)
+    }
+
+    fn summary(&self) -> &'static str {
+        i18n::lookup(self.summary)
+    }
+
+    fn source(&self) -> &'static str {
+        self.source
+    }
+
+    fn run(&self) {
+        (self.run)()
+    }
+
+    fn example(&self) {
+        (self.example)()
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        self.exercises
+    }
+}
@@ -0,0 +1,368 @@
+// 课程中的代码清单
+// 课程展示的示例代码写成真实的 Rust，随课程一起编译 (只展示不调用的函数也经过类型检查)，
+// 用注释标出要展示的部分，运行时从 include_str! 得到的源码中取出打印:
+//
+//   // region: greet
+//   async fn greet(name: &str) -> String { ... }
+//   // endregion: greet
+//
+//   print_region(LESSON.source, "greet");
+//
+// 示例依赖的辅助定义 (如模拟的 fetch_data) 放在标记之外，不出现在清单中；
+// 同名的示例 (如多个 main) 放进各自的私有 mod，清单会去掉公共缩进。
+// 代码块按输出格式渲染：终端中语法高亮，Markdown 为 ```rust 代码块，HTML 为带 class 的 <span>
+
+use super::output;
+use crate::i18n;
+
+// 源码中 // region: 名称 与 // endregion: 名称 之间的代码，去掉公共缩进；
+// 区域可以嵌套，内层的标记行不会出现在结果中
+pub fn region(source: &str, name: &str) -> Option<String> {
+    let start = format!("// region: {}", name);
+    let end = format!("// endregion: {}", name);
+    let mut lines = source.lines();
+    lines.by_ref().find(|line| line.trim() == start)?;
+    let mut code = Vec::new();
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed == end {
+            return Some(dedent(&code));
+        }
+        if !trimmed.starts_with("// region: ") && !trimmed.starts_with("// endregion: ") {
+            code.push(line);
+        }
+    }
+    None
+}
+
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let lines: Vec<&str> = lines.iter().map(|line| line.get(indent..).unwrap_or("")).collect();
+    lines.join("\n")
+}
+
+// 打印一段代码清单，其中的中文注释和字符串按当前语言翻译
+// 标记缺失或没有结束时打印一行说明代替清单，不中断课程 (测试会检查所有清单都能找到)
+pub fn print_region(source: &str, name: &str) {
+    output::print_code(&listing(source, name));
+}
+
+fn listing(source: &str, name: &str) -> String {
+    let Some(code) = region(source, name) else {
+        return i18n::tr!("// 源码中没有完整的 region {}", name);
+    };
+    let lines: Vec<String> = code.lines().map(i18n::code).collect();
+    lines.join("\n")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    Keyword,
+    // 大写开头的标识符：类型、trait、枚举成员
+    Type,
+    Macro,
+    String,
+    Number,
+    Comment,
+    Attribute,
+    Lifetime,
+    Plain,
+}
+
+impl Token {
+    pub fn ansi(self) -> Option<&'static str> {
+        match self {
+            Token::Keyword => Some("\x1b[35m"),
+            Token::Type => Some("\x1b[36m"),
+            Token::Macro => Some("\x1b[34m"),
+            Token::String => Some("\x1b[32m"),
+            Token::Number | Token::Lifetime => Some("\x1b[33m"),
+            Token::Comment => Some("\x1b[90m"),
+            Token::Attribute => Some("\x1b[2m"),
+            Token::Plain => None,
+        }
+    }
+
+    // HTML 中的 class，颜色见 learn export 生成的 style.css
+    pub fn class(self) -> Option<&'static str> {
+        match self {
+            Token::Keyword => Some("kw"),
+            Token::Type => Some("ty"),
+            Token::Macro => Some("ma"),
+            Token::String => Some("st"),
+            Token::Number => Some("nu"),
+            Token::Comment => Some("co"),
+            Token::Attribute => Some("at"),
+            Token::Lifetime => Some("lt"),
+            Token::Plain => None,
+        }
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+];
+
+// 足够展示课程代码的简单词法分析，不做完整的 Rust 解析
+pub fn tokenize(code: &str) -> Vec<(Token, &str)> {
+    let bytes = code.as_bytes();
+    let mut tokens: Vec<(Token, &str)> = Vec::new();
+    let mut start = 0;
+    while start < bytes.len() {
+        let rest = &code[start..];
+        let (token, len) = match bytes[start] {
+            b'/' if rest.starts_with("//") => (Token::Comment, rest.find('\n').unwrap_or(rest.len())),
+            b'/' if rest.starts_with("/*") => (Token::Comment, rest.find("*/").map_or(rest.len(), |end| end + 2)),
+            b'"' => (Token::String, string_len(rest, 0)),
+            b'r' | b'b' if raw_string_prefix(rest).is_some() => {
+                let prefix = raw_string_prefix(rest).unwrap_or(0);
+                (Token::String, string_len(rest, prefix))
+            }
+            b'\'' => quote(rest),
+            b'#' if rest.starts_with("#[") || rest.starts_with("#![") => (Token::Attribute, attribute_len(rest)),
+            b'0'..=b'9' => (Token::Number, number_len(rest)),
+            c if c == b'_' || c.is_ascii_alphabetic() => {
+                let len = rest
+                    .find(|c: char| !(c == '_' || c.is_ascii_alphanumeric()))
+                    .unwrap_or(rest.len());
+                let word = &rest[..len];
+                if rest[len..].starts_with('!') && !rest[len..].starts_with("!=") {
+                    (Token::Macro, len + 1)
+                } else if KEYWORDS.contains(&word) {
+                    (Token::Keyword, len)
+                } else if word.starts_with(|c: char| c.is_ascii_uppercase()) {
+                    (Token::Type, len)
+                } else {
+                    (Token::Plain, len)
+                }
+            }
+            _ => (Token::Plain, rest.chars().next().map_or(1, char::len_utf8)),
+        };
+        let text = &code[start..start + len];
+        // 相邻的普通文本合并为一段
+        match tokens.last_mut() {
+            Some((Token::Plain, last)) if token == Token::Plain => {
+                *last = &code[start - last.len()..start + len];
+            }
+            _ => tokens.push((token, text)),
+        }
+        start += len;
+    }
+    tokens
+}
+
+// r"..." r#"..."# b"..." br"..." 的前缀长度 (到引号之前)
+fn raw_string_prefix(text: &str) -> Option<usize> {
+    let after = text.strip_prefix("br").or_else(|| text.strip_prefix('r')).or_else(|| text.strip_prefix('b'))?;
+    let hashes = after.len() - after.trim_start_matches('#').len();
+    after[hashes..].starts_with('"').then_some(text.len() - after.len() + hashes)
+}
+
+// 从 prefix 处的引号开始到对应的结束引号 (含)，原始字符串要匹配同样数量的 #
+fn string_len(text: &str, prefix: usize) -> usize {
+    let hashes = text[..prefix].matches('#').count();
+    let raw = prefix > 0 && text[..prefix].contains('r');
+    let closing = format!("\"{}", "#".repeat(hashes));
+    let body = &text[prefix + 1..];
+    let mut chars = body.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' if !raw => {
+                chars.next();
+            }
+            '"' if body[index..].starts_with(&closing) => return prefix + 1 + index + closing.len(),
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+// 'a' 和 '\n' 是字符，'a 和 'static 是生命周期
+fn quote(text: &str) -> (Token, usize) {
+    let mut chars = text.char_indices().skip(1);
+    match chars.next() {
+        Some((_, '\\')) => {
+            // 跳过被转义的字符，'\'' 中的第二个引号不是结尾
+            let end = text.get(3..).and_then(|rest| rest.find('\'')).map_or(text.len(), |end| end + 4);
+            (Token::String, end)
+        }
+        Some((_, c)) => {
+            let after = 1 + c.len_utf8();
+            if text[after..].starts_with('\'') {
+                (Token::String, after + 1)
+            } else {
+                let len = text[1..]
+                    .find(|c: char| !(c == '_' || c.is_ascii_alphanumeric()))
+                    .map_or(text.len(), |end| end + 1);
+                (Token::Lifetime, len)
+            }
+        }
+        None => (Token::Plain, 1),
+    }
+}
+
+fn attribute_len(text: &str) -> usize {
+    let mut depth = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return index + 1;
+                }
+            }
+            '\n' => return index,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+// 数字可以带下划线、后缀 (10u64) 和小数部分，1..5 中的 .. 不属于数字
+fn number_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut len = 0;
+    while len < bytes.len() {
+        let c = bytes[len];
+        let fraction = c == b'.' && bytes.get(len + 1).is_some_and(u8::is_ascii_digit);
+        if c == b'_' || c.is_ascii_alphanumeric() || fraction {
+            len += 1;
+        } else {
+            break;
+        }
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::learn::LESSONS;
+
+    #[test]
+    fn raw_strings_end_at_matching_hashes() {
+        let code = r###"let s = r#"a "quoted" b"#;"###;
+        assert_eq!(
+            tokenize(code),
+            vec![
+                (Token::Keyword, "let"),
+                (Token::Plain, " s = "),
+                (Token::String, r###"r#"a "quoted" b"#"###),
+                (Token::Plain, ";"),
+            ]
+        );
+        // 原始字符串中的反斜杠不是转义
+        assert_eq!(tokenize(r#"br"\" x"#)[0], (Token::String, r#"br"\""#));
+        // r 和 b 开头的标识符不是字符串
+        assert_eq!(tokenize("return raw + bytes"), vec![(Token::Keyword, "return"), (Token::Plain, " raw + bytes")]);
+    }
+
+    #[test]
+    fn chars_and_lifetimes() {
+        assert_eq!(
+            tokenize("'a' &'a str '\\n' 'static"),
+            vec![
+                (Token::String, "'a'"),
+                (Token::Plain, " &"),
+                (Token::Lifetime, "'a"),
+                (Token::Plain, " str "),
+                (Token::String, "'\\n'"),
+                (Token::Plain, " "),
+                (Token::Lifetime, "'static"),
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_quote_is_one_char() {
+        assert_eq!(
+            tokenize("('\\'', 'b')"),
+            vec![
+                (Token::Plain, "("),
+                (Token::String, "'\\''"),
+                (Token::Plain, ", "),
+                (Token::String, "'b'"),
+                (Token::Plain, ")"),
+            ]
+        );
+    }
+
+    #[test]
+    fn ranges_are_not_fractions() {
+        assert_eq!(
+            tokenize("1..5 1.5 10_000u64"),
+            vec![
+                (Token::Number, "1"),
+                (Token::Plain, ".."),
+                (Token::Number, "5"),
+                (Token::Plain, " "),
+                (Token::Number, "1.5"),
+                (Token::Plain, " "),
+                (Token::Number, "10_000u64"),
+            ]
+        );
+    }
+
+    #[test]
+    fn not_equal_is_not_a_macro() {
+        assert_eq!(tokenize("x!=y"), vec![(Token::Plain, "x!=y")]);
+        assert_eq!(
+            tokenize("vec![1]"),
+            vec![(Token::Macro, "vec!"), (Token::Plain, "["), (Token::Number, "1"), (Token::Plain, "]")]
+        );
+    }
+
+    #[test]
+    fn nested_regions_drop_inner_markers() {
+        let source = "\
+fn outer() {
+    // region: outer
+    let a = 1;
+    // region: inner
+    let b = 2;
+    // endregion: inner
+    // endregion: outer
+}";
+        assert_eq!(region(source, "outer").as_deref(), Some("let a = 1;\nlet b = 2;"));
+        assert_eq!(region(source, "inner").as_deref(), Some("let b = 2;"));
+    }
+
+    #[test]
+    fn unterminated_or_missing_regions_are_none() {
+        let source = "// region: open\nlet a = 1;\n";
+        assert_eq!(region(source, "open"), None);
+        assert_eq!(region(source, "missing"), None);
+        // 打印时用一行说明代替清单，不 panic
+        assert!(listing(source, "open").contains("region open"));
+    }
+
+    #[test]
+    fn dedent_keeps_relative_indent() {
+        assert_eq!(dedent(&["    if x {", "        y();", "", "    }"]), "if x {\n    y();\n\n}");
+        assert_eq!(dedent(&[]), "");
+    }
+
+    // 课程中每个 print_region 都要能取到完整的清单
+    #[test]
+    fn every_listing_in_lessons_exists() {
+        let call = regex::Regex::new(r#"print_region\(LESSON\.source, "([^"]+)"\)"#).unwrap();
+        for lesson in LESSONS {
+            for name in call.captures_iter(lesson.source()) {
+                assert!(
+                    region(lesson.source(), &name[1]).is_some(),
+                    "第{}课没有完整的 region {}",
+                    lesson.id(),
+                    &name[1]
+                );
+            }
+        }
+    }
+}
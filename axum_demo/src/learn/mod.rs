@@ -1,24 +1,196 @@
 // Rust 学习模块
 // 基于 C/Python/TypeScript 背景的系统化学习路径
 
-// 阶段一：基础语法 (01-20)
-pub mod _01_variables_and_types;    // 变量与类型
-pub mod _02_constants_and_static;   // 常量与静态变量
-pub mod _03_basic_operators;        // 基本操作符
-pub mod _04_control_flow;           // 控制流
-pub mod _05_functions;              // 函数
-
-// 阶段二：所有权系统 (21-40)
-pub mod _21_ownership_basics;       // 所有权基础
-pub mod _23_borrowing;              // 借用与引用
-
-// 阶段三：数据结构 (41-60)
-pub mod _41_structs;                // 结构体
-pub mod _42_enums;                  // 枚举
-
-// 阶段四：高级特性 (61-80)
-pub mod _61_error_handling;         // 错误处理
-
-// 阶段五：实用开发 (81-99)
-pub mod _84_async_basics;           // 异步基础
-pub mod _88_web_server;             // Web 服务器
+// 教学代码：刻意保留的写法（未使用的字段、显式 return、&String 参数、近似常量等）
+// 是为了演示概念，不按 clippy 的建议改写
+#![allow(dead_code)]
+#![allow(
+    clippy::approx_constant,
+    clippy::needless_return,
+    clippy::let_and_return,
+    clippy::ptr_arg,
+    clippy::redundant_slicing,
+    clippy::single_char_add_str,
+    clippy::unnecessary_literal_unwrap,
+    clippy::unnecessary_lazy_evaluations,
+    clippy::useless_vec,
+    clippy::println_empty_string
+)]
+
+// 课程中的 println! / print! 写入 output 模块中当前线程的输出，
+// 而不是直接写标准输出，并按 --lang 翻译 (见 i18n 模块)；
+// 宏按文本顺序生效，必须定义在课程模块声明之前
+macro_rules! println {
+    () => {
+        $crate::learn::output::print(format_args!("\n"))
+    };
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::learn::output::print(format_args!("{}\n", $crate::i18n::tr!($fmt $($arg)*)))
+    };
+}
+
+macro_rules! print {
+    ($fmt:literal $($arg:tt)*) => {
+        $crate::learn::output::print(format_args!("{}", $crate::i18n::tr!($fmt $($arg)*)))
+    };
+}
+
+pub mod book;
+pub mod exercise;
+pub mod lesson;
+pub mod listing;
+pub mod output;
+pub mod progress;
+pub mod tui;
+
+pub use lesson::{Lesson, Stage};
+
+// 登记课程：声明模块并加入 LESSONS，模块中需定义 pub const LESSON
+macro_rules! lessons {
+    ($($module:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        // 按编号排列的全部课程
+        pub static LESSONS: &[&dyn Lesson] = &[$(&$module::LESSON),*];
+    };
+}
+
+lessons! {
+    // 阶段一：基础语法 (01-20)
+    _01_variables_and_types,    // 变量与类型
+    _02_constants_and_static,   // 常量与静态变量
+    _03_basic_operators,        // 基本操作符
+    _04_control_flow,           // 控制流
+    _05_functions,              // 函数
+
+    // 阶段二：所有权系统 (21-40)
+    _21_ownership_basics,       // 所有权基础
+    _23_borrowing,              // 借用与引用
+
+    // 阶段三：数据结构 (41-60)
+    _41_structs,                // 结构体
+    _42_enums,                  // 枚举
+    _43_traits_generics,        // Trait 与泛型
+
+    // 阶段四：高级特性 (61-80)
+    _61_error_handling,         // 错误处理
+    _62_concurrency,            // 并发编程
+
+    // 阶段五：实用开发 (81-99)
+    _84_async_basics,           // 异步基础
+    _88_web_server,             // Web 服务器
+}
+
+pub fn find(id: &str) -> Option<&'static dyn Lesson> {
+    LESSONS.iter().copied().find(|lesson| lesson.id() == id)
+}
+
+pub fn ids() -> Vec<&'static str> {
+    LESSONS.iter().map(|lesson| lesson.id()).collect()
+}
+
+// 课程中可以直接启动的示例服务器 (learn <编号> --serve)
+pub fn server(lesson: &dyn Lesson) -> Option<fn() -> std::io::Result<()>> {
+    match lesson.id() {
+        "88" => Some(_88_web_server::serve),
+        _ => None,
+    }
+}
+
+// 运行一课：讲解 + 可运行示例
+pub fn run(lesson: &dyn Lesson) {
+    println!("🦀 第{}课：{}", lesson.id(), lesson.title());
+    lesson.run();
+    println!("\n--- 可运行示例 ---");
+    lesson.example();
+
+    let exercises = lesson.exercises().len();
+    if exercises > 0 {
+        println!();
+        println!("📝 本课有 {} 道练习: cargo run learn exercise {}", exercises, lesson.id());
+    }
+}
+
+// 运行并记录学习进度，进度文件读写失败只提示，不影响课程
+pub fn run_tracked(lesson: &dyn Lesson) {
+    run_tracked_with(lesson, &mut |warning| eprintln!("{}", warning));
+}
+
+// 提示交给 warn 处理，终端界面中显示在状态行，不写 stderr
+pub fn run_tracked_with(lesson: &dyn Lesson, warn: &mut dyn FnMut(String)) {
+    let mut progress = match progress::Progress::load() {
+        Ok(progress) => Some(progress),
+        Err(e) => {
+            warn(crate::i18n::tr!("⚠️ 无法读取学习进度: {}", e));
+            None
+        }
+    };
+    if let Some(progress) = progress.as_mut() {
+        progress.start(lesson.id());
+        save_progress(progress, warn);
+    }
+
+    run(lesson);
+
+    if let Some(progress) = progress.as_mut() {
+        progress.complete(lesson.id());
+        save_progress(progress, warn);
+    }
+}
+
+fn save_progress(progress: &progress::Progress, warn: &mut dyn FnMut(String)) {
+    if let Err(e) = progress.save() {
+        warn(crate::i18n::tr!("⚠️ 无法保存学习进度: {}", e));
+    }
+}
+
+pub fn show_lessons() {
+    println!("=== Rust 学习路径 ===");
+    println!();
+
+    // 标题列至少 22 列，英文标题较长时按最长的对齐
+    let width = LESSONS.iter().map(|lesson| display_width(lesson.title()) + 2).max().unwrap_or(0).max(22);
+
+    for stage in Stage::ALL {
+        println!("{} {}", stage.icon(), stage);
+        for lesson in LESSONS.iter().filter(|lesson| lesson.stage() == stage) {
+            let title = pad(lesson.title(), width);
+            println!("  {} - {} - {}", lesson.id(), title, lesson.summary());
+        }
+        println!("  其余课程 (待实现)");
+        println!();
+    }
+
+    println!("使用方法:");
+    for lesson in LESSONS.iter().filter(|lesson| ["01", "21", "88"].contains(&lesson.id())) {
+        println!("  cargo run learn {}   # 运行第{}课", lesson.id(), lesson.id());
+    }
+    println!();
+    println!("💡 提示：每个课程都包含详细的对比说明和可运行的示例代码");
+}
+
+// Markdown 学习清单，README 中的「学习进度跟踪」由此生成
+pub fn checklist() -> String {
+    let mut text = String::new();
+    for stage in Stage::ALL {
+        let lessons: Vec<_> = LESSONS.iter().filter(|lesson| lesson.stage() == stage).collect();
+        if lessons.is_empty() {
+            continue;
+        }
+        text.push_str(&format!("### {}\n", stage));
+        for lesson in lessons {
+            text.push_str(&format!("- [ ] {} - {}\n", lesson.id(), lesson.title()));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+// 按终端显示宽度补齐，中文字符占两列
+fn pad(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(display_width(text))))
+}
+
+fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum()
+}
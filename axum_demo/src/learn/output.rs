@@ -0,0 +1,279 @@
+// 课程输出
+// learn 模块内的 println! / print! 被替换为写入当前线程的 Output，
+// 课程代码保持普通的 println! 写法 (示例仍可直接复制到 main.rs 运行)，
+// 输出却可以写到终端、带颜色、转成 Markdown / HTML 或捕获为字符串
+//
+// 行的约定 (各课程已遵守)，不同格式据此渲染标题:
+//   🦀 第01课：变量与类型      - 课程标题
+//   === 01. 变量与类型 ===     - 一级小节
+//   --- 不可变借用 ---         - 二级小节
+// 代码清单 (listing::print_region) 不按行的约定处理，单独渲染为代码块 (见 print_code)
+//
+// 输出绑定在线程上，课程中新开线程的打印不会被捕获
+
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::rc::Rc;
+
+use super::listing;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    // 原样输出
+    Plain,
+    // 标题加 ANSI 颜色
+    Color,
+    // 标题转为 Markdown 标题，正文放进 text 代码块
+    Markdown,
+    // 标题转为 <h1>-<h3>，正文放进 <pre>，用于导出课程手册
+    Html,
+}
+
+impl Format {
+    // 终端且未设置 NO_COLOR 时使用颜色
+    pub fn detect() -> Self {
+        if io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none() {
+            Format::Color
+        } else {
+            Format::Plain
+        }
+    }
+}
+
+enum Line<'a> {
+    Title(&'a str),
+    Section(&'a str),
+    Subsection(&'a str),
+    Text(&'a str),
+}
+
+impl<'a> Line<'a> {
+    fn classify(line: &'a str) -> Self {
+        if let Some(title) = line.strip_prefix("🦀 ") {
+            return Line::Title(title);
+        }
+        if let Some(inner) = line.strip_prefix("=== ").and_then(|l| l.strip_suffix(" ===")) {
+            return Line::Section(inner);
+        }
+        if let Some(inner) = line.strip_prefix("--- ").and_then(|l| l.strip_suffix(" ---")) {
+            return Line::Subsection(inner);
+        }
+        Line::Text(line)
+    }
+}
+
+pub struct Output {
+    writer: Box<dyn Write>,
+    format: Format,
+    // print! 写入的、还没有换行的部分
+    pending: String,
+    // Markdown / HTML 是否处于代码块中
+    in_block: bool,
+    // 代码块中暂缓写出的空行，代码块结束时丢弃
+    blank_lines: usize,
+}
+
+impl Output {
+    pub fn new(writer: impl Write + 'static, format: Format) -> Self {
+        Output {
+            writer: Box::new(writer),
+            format,
+            pending: String::new(),
+            in_block: false,
+            blank_lines: 0,
+        }
+    }
+
+    pub fn stdout(format: Format) -> Self {
+        Output::new(io::stdout(), format)
+    }
+
+    fn write_str(&mut self, text: &str) -> io::Result<()> {
+        self.pending.push_str(text);
+        while let Some(end) = self.pending.find('\n') {
+            let line: String = self.pending.drain(..=end).collect();
+            self.write_line(line.trim_end_matches('\n'))?;
+        }
+        Ok(())
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        match self.format {
+            Format::Plain => writeln!(self.writer, "{}", line),
+            Format::Color => match Line::classify(line) {
+                Line::Title(_) => writeln!(self.writer, "\x1b[1;32m{}\x1b[0m", line),
+                Line::Section(_) => writeln!(self.writer, "\x1b[1;36m{}\x1b[0m", line),
+                Line::Subsection(_) => writeln!(self.writer, "\x1b[1;33m{}\x1b[0m", line),
+                Line::Text(_) => writeln!(self.writer, "{}", line),
+            },
+            Format::Markdown | Format::Html => match Line::classify(line) {
+                Line::Title(title) => self.heading(1, title),
+                Line::Section(title) => self.heading(2, title),
+                Line::Subsection(title) => self.heading(3, title),
+                Line::Text("") => {
+                    if self.in_block {
+                        self.blank_lines += 1;
+                    }
+                    Ok(())
+                }
+                Line::Text(text) => {
+                    if !self.in_block {
+                        match self.format {
+                            Format::Html => write!(self.writer, "<pre>")?,
+                            _ => writeln!(self.writer, "```text")?,
+                        }
+                        self.in_block = true;
+                    }
+                    for _ in 0..std::mem::take(&mut self.blank_lines) {
+                        writeln!(self.writer)?;
+                    }
+                    match self.format {
+                        Format::Html => writeln!(self.writer, "{}", escape_html(text)),
+                        _ => writeln!(self.writer, "{}", text),
+                    }
+                }
+            },
+        }
+    }
+
+    fn heading(&mut self, level: usize, title: &str) -> io::Result<()> {
+        self.close_block()?;
+        match self.format {
+            Format::Html => writeln!(self.writer, "<h{0}>{1}</h{0}>", level, escape_html(title)),
+            _ => writeln!(self.writer, "{} {}\n", "#".repeat(level), title),
+        }
+    }
+
+    fn close_block(&mut self) -> io::Result<()> {
+        if self.in_block {
+            match self.format {
+                Format::Html => writeln!(self.writer, "</pre>")?,
+                _ => writeln!(self.writer, "```\n")?,
+            }
+            self.in_block = false;
+            self.blank_lines = 0;
+        }
+        Ok(())
+    }
+
+    fn write_code(&mut self, code: &str) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            let rest = std::mem::take(&mut self.pending);
+            self.write_line(&rest)?;
+        }
+        match self.format {
+            Format::Plain => writeln!(self.writer, "{}", code),
+            Format::Color => {
+                for (token, text) in listing::tokenize(code) {
+                    match token.ansi() {
+                        Some(color) => write!(self.writer, "{}{}\x1b[0m", color, text)?,
+                        None => write!(self.writer, "{}", text)?,
+                    }
+                }
+                writeln!(self.writer)
+            }
+            Format::Markdown => {
+                self.close_block()?;
+                writeln!(self.writer, "```rust\n{}\n```\n", code)
+            }
+            Format::Html => {
+                self.close_block()?;
+                write!(self.writer, "<pre class=\"rust\"><code>")?;
+                for (token, text) in listing::tokenize(code) {
+                    match token.class() {
+                        Some(class) => write!(self.writer, "<span class=\"{}\">{}</span>", class, escape_html(text))?,
+                        None => write!(self.writer, "{}", escape_html(text))?,
+                    }
+                }
+                writeln!(self.writer, "</code></pre>")
+            }
+        }
+    }
+
+    // 写出未换行的内容并关闭代码块
+    fn finish(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            let rest = std::mem::take(&mut self.pending);
+            self.write_line(&rest)?;
+        }
+        self.close_block()?;
+        self.writer.flush()
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Output>> = const { RefCell::new(None) };
+}
+
+// 在 f 执行期间把课程输出写到 output，结束后恢复之前的输出
+pub fn render<R>(output: Output, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.borrow_mut().replace(output));
+    let result = f();
+    CURRENT.with(|current| {
+        let mut current = current.borrow_mut();
+        if let Some(mut output) = current.take() {
+            // 输出端已关闭 (如管道被关闭) 时不再报错
+            output.finish().ok();
+        }
+        *current = previous;
+    });
+    result
+}
+
+// 捕获为字符串，用于测试或 HTTP 响应
+pub fn capture(format: Format, f: impl FnOnce()) -> String {
+    let buffer = Buffer::default();
+    render(Output::new(buffer.clone(), format), f);
+    let bytes = buffer.0.borrow();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+// println! / print! 的实际实现，未设置输出时按原样写到标准输出
+pub fn print(args: fmt::Arguments) {
+    // 先格式化再借用输出，Display 实现中再次打印时不会重复借用
+    let text = args.to_string();
+    CURRENT.with(|current| {
+        let mut current = current.borrow_mut();
+        let result = match current.as_mut() {
+            Some(output) => output.write_str(&text),
+            None => io::stdout().write_all(text.as_bytes()),
+        };
+        // 与 std 的 println! 一致，写失败时 panic
+        if let Err(e) = result {
+            panic!("failed printing lesson output: {}", e);
+        }
+    });
+}
+
+// 打印一段 Rust 代码：终端中高亮，Markdown / HTML 中放进单独的代码块
+pub fn print_code(code: &str) {
+    CURRENT.with(|current| {
+        let mut current = current.borrow_mut();
+        let result = match current.as_mut() {
+            Some(output) => output.write_code(code),
+            None => writeln!(io::stdout(), "{}", code),
+        };
+        if let Err(e) = result {
+            panic!("failed printing lesson output: {}", e);
+        }
+    });
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[derive(Clone, Default)]
+struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
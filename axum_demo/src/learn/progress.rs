@@ -0,0 +1,347 @@
+// 学习进度
+// 记录每课的运行情况，保存在用户数据目录下的 JSON 文件中:
+//   Linux   ~/.local/share/axum_demo/progress.json
+//   macOS   ~/Library/Application Support/axum_demo/progress.json
+//   Windows %APPDATA%\axum_demo\progress.json
+// 设置 AXUM_DEMO_DATA_DIR 可以改用其他目录 (测试、容器中使用)
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::{exercise, Lesson, Stage, LESSONS};
+use crate::i18n::{self, tr};
+
+pub const DATA_DIR_ENV: &str = "AXUM_DEMO_DATA_DIR";
+const FILE_NAME: &str = "progress.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    // 开始运行但没有正常结束 (如中途 panic)
+    Started,
+    // 讲解和示例都运行完毕
+    Completed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    pub status: Status,
+    // 运行次数
+    pub runs: u32,
+    // 最近一次运行的 Unix 时间戳 (秒)
+    pub last_run: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExerciseRecord {
+    // 通过一次即记为通过
+    pub passed: bool,
+    pub attempts: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Progress {
+    // 课程编号 -> 记录
+    pub lessons: BTreeMap<String, Record>,
+    // 练习编号 (如 23.2) -> 记录
+    #[serde(default)]
+    pub exercises: BTreeMap<String, ExerciseRecord>,
+}
+
+// 进度文件和练习代码所在目录
+pub fn data_dir() -> io::Result<PathBuf> {
+    data_dir_from(std::env::var_os(DATA_DIR_ENV))
+}
+
+// dir 为 AXUM_DEMO_DATA_DIR 的值，与进程环境分开以便测试
+fn data_dir_from(dir: Option<OsString>) -> io::Result<PathBuf> {
+    match dir {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => Ok(dirs::data_dir()
+            .ok_or_else(|| io::Error::other("无法确定用户数据目录，请设置 AXUM_DEMO_DATA_DIR"))?
+            .join("axum_demo")),
+    }
+}
+
+impl Progress {
+    pub fn path() -> io::Result<PathBuf> {
+        Ok(data_dir()?.join(FILE_NAME))
+    }
+
+    // 文件不存在时返回空进度
+    pub fn load() -> io::Result<Self> {
+        Self::load_from(&Self::path()?)
+    }
+
+    fn load_from(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e))
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Progress::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        self.save_to(&Self::path()?)
+    }
+
+    fn save_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let text = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        std::fs::write(path, text + "\n")
+    }
+
+    // 删除进度文件，返回是否存在过
+    pub fn reset() -> io::Result<bool> {
+        Self::reset_at(&Self::path()?)
+    }
+
+    fn reset_at(path: &Path) -> io::Result<bool> {
+        match std::fs::remove_file(path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn status(&self, id: &str) -> Option<Status> {
+        self.lessons.get(id).map(|record| record.status)
+    }
+
+    // 开始运行时记为 Started，已完成的课程重新运行时保持 Completed
+    pub fn start(&mut self, id: &str) {
+        let record = self.lessons.entry(id.to_string()).or_insert(Record {
+            status: Status::Started,
+            runs: 0,
+            last_run: 0,
+        });
+        record.runs += 1;
+        record.last_run = now();
+    }
+
+    pub fn complete(&mut self, id: &str) {
+        if let Some(record) = self.lessons.get_mut(id) {
+            record.status = Status::Completed;
+        }
+    }
+
+    pub fn record_exercise(&mut self, key: &str, passed: bool) {
+        let record = self.exercises.entry(key.to_string()).or_insert(ExerciseRecord {
+            passed: false,
+            attempts: 0,
+        });
+        record.attempts += 1;
+        record.passed |= passed;
+    }
+
+    // 本课已通过的练习数
+    pub fn exercises_passed(&self, lesson: &dyn Lesson) -> usize {
+        (0..lesson.exercises().len())
+            .filter(|&index| {
+                self.exercises
+                    .get(&exercise::key(lesson, index))
+                    .is_some_and(|record| record.passed)
+            })
+            .count()
+    }
+
+    // 按编号顺序第一个未完成的课程
+    pub fn next(&self) -> Option<&'static dyn Lesson> {
+        LESSONS
+            .iter()
+            .copied()
+            .find(|lesson| self.status(lesson.id()) != Some(Status::Completed))
+    }
+
+    pub fn completed_count(&self, lessons: &[&dyn Lesson]) -> usize {
+        lessons
+            .iter()
+            .filter(|lesson| self.status(lesson.id()) == Some(Status::Completed))
+            .count()
+    }
+
+    // 按阶段分组显示，与课程列表相同
+    pub fn show(&self) {
+        println!("=== 学习进度 ===");
+        println!();
+
+        for stage in Stage::ALL {
+            let lessons: Vec<&dyn Lesson> = LESSONS
+                .iter()
+                .copied()
+                .filter(|lesson| lesson.stage() == stage)
+                .collect();
+            println!(
+                "{} {}  [{}/{}]",
+                stage.icon(),
+                stage,
+                self.completed_count(&lessons),
+                lessons.len()
+            );
+            for lesson in lessons {
+                let (mark, note) = match self.lessons.get(lesson.id()) {
+                    Some(record) if record.status == Status::Completed => {
+                        ("✅", tr!("  运行 {} 次", record.runs))
+                    }
+                    Some(_) => ("🔄", i18n::lookup("  未运行完").to_string()),
+                    None => ("⬜", String::new()),
+                };
+                let exercises = match lesson.exercises().len() {
+                    0 => String::new(),
+                    total => tr!("  练习 {}/{}", self.exercises_passed(lesson), total),
+                };
+                println!("  {} {} - {}{}{}", mark, lesson.id(), lesson.title(), note, exercises);
+            }
+            println!();
+        }
+
+        let done = self.completed_count(LESSONS);
+        println!("总进度: {}/{} ({}%)", done, LESSONS.len(), done * 100 / LESSONS.len().max(1));
+        match self.next() {
+            Some(lesson) => println!("下一课: {} - {}  (cargo run learn next)", lesson.id(), lesson.title()),
+            None => println!("🎉 所有课程都已完成"),
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 每个测试使用自己的数据目录，与 AXUM_DEMO_DATA_DIR 指向的目录结构相同
+    fn progress_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("axum_demo-progress-tests").join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        data_dir_from(Some(dir.into_os_string())).unwrap().join(FILE_NAME)
+    }
+
+    fn lesson_with_exercises() -> &'static dyn Lesson {
+        LESSONS.iter().copied().find(|lesson| lesson.exercises().len() >= 2).unwrap()
+    }
+
+    #[test]
+    fn data_dir_env_var_is_used() {
+        let dir = std::env::temp_dir().join("axum_demo-data");
+        assert_eq!(data_dir_from(Some(dir.clone().into_os_string())).unwrap(), dir);
+    }
+
+    #[test]
+    fn missing_file_is_empty_progress() {
+        let progress = Progress::load_from(&progress_file("missing")).unwrap();
+        assert!(progress.lessons.is_empty() && progress.exercises.is_empty());
+    }
+
+    #[test]
+    fn corrupt_file_is_invalid_data() {
+        let path = progress_file("corrupt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{\"lessons\": [").unwrap();
+        let error = Progress::load_from(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains(&path.display().to_string()), "{}", error);
+    }
+
+    // 早期的进度文件没有 exercises
+    #[test]
+    fn file_without_exercises_loads() {
+        let path = progress_file("old");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, r#"{"lessons": {"01": {"status": "completed", "runs": 2, "last_run": 1}}}"#).unwrap();
+        let progress = Progress::load_from(&path).unwrap();
+        assert_eq!(progress.status("01"), Some(Status::Completed));
+        assert!(progress.exercises.is_empty());
+    }
+
+    #[test]
+    fn saved_progress_loads_back_and_reset_removes_it() {
+        let path = progress_file("round-trip");
+        let mut progress = Progress::default();
+        progress.start("01");
+        progress.complete("01");
+        progress.start("02");
+        progress.record_exercise("23.1", true);
+        // 目录不存在时自动创建
+        progress.save_to(&path).unwrap();
+
+        let loaded = Progress::load_from(&path).unwrap();
+        assert_eq!(loaded.status("01"), Some(Status::Completed));
+        assert_eq!(loaded.status("02"), Some(Status::Started));
+        assert!(loaded.exercises["23.1"].passed);
+
+        assert!(Progress::reset_at(&path).unwrap());
+        assert!(!Progress::reset_at(&path).unwrap());
+        assert!(Progress::load_from(&path).unwrap().lessons.is_empty());
+    }
+
+    #[test]
+    fn start_counts_runs_and_keeps_completed() {
+        let mut progress = Progress::default();
+        progress.start("01");
+        assert_eq!(progress.status("01"), Some(Status::Started));
+        progress.complete("01");
+        progress.start("01");
+        let record = &progress.lessons["01"];
+        assert_eq!((record.status, record.runs), (Status::Completed, 2));
+        assert!(record.last_run > 0);
+    }
+
+    #[test]
+    fn complete_without_start_is_ignored() {
+        let mut progress = Progress::default();
+        progress.complete("01");
+        assert_eq!(progress.status("01"), None);
+    }
+
+    #[test]
+    fn next_is_the_first_unfinished_lesson() {
+        let mut progress = Progress::default();
+        assert_eq!(progress.next().map(|lesson| lesson.id()), Some(LESSONS[0].id()));
+
+        // 只开始没完成的课程仍然是下一课
+        progress.start(LESSONS[0].id());
+        assert_eq!(progress.next().map(|lesson| lesson.id()), Some(LESSONS[0].id()));
+
+        progress.complete(LESSONS[0].id());
+        assert_eq!(progress.next().map(|lesson| lesson.id()), Some(LESSONS[1].id()));
+
+        for lesson in LESSONS {
+            progress.start(lesson.id());
+            progress.complete(lesson.id());
+        }
+        assert!(progress.next().is_none());
+        assert_eq!(progress.completed_count(LESSONS), LESSONS.len());
+    }
+
+    #[test]
+    fn exercise_stays_passed_after_a_failed_attempt() {
+        let lesson = lesson_with_exercises();
+        let first = exercise::key(lesson, 0);
+        let second = exercise::key(lesson, 1);
+        let mut progress = Progress::default();
+
+        progress.record_exercise(&first, false);
+        progress.record_exercise(&first, true);
+        progress.record_exercise(&first, false);
+        progress.record_exercise(&second, false);
+
+        let record = &progress.exercises[&first];
+        assert_eq!((record.passed, record.attempts), (true, 3));
+        assert_eq!(progress.exercises_passed(lesson), 1);
+    }
+}
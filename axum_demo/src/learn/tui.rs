@@ -0,0 +1,353 @@
+// 终端课程浏览器 (learn --tui)
+// 左侧按阶段列出课程和进度，右侧显示说明与语言对比，或运行课程后的输出
+//
+//   ↑/↓ j/k    选择课程
+//   Enter      运行课程并记录进度
+//   Tab        切换 说明 / 输出
+//   PgUp/PgDn  滚动右侧内容
+//   q / Esc    退出
+
+use std::io::{self, IsTerminal};
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use super::output::{self, Format};
+use super::progress::{Progress, Status};
+use super::{Lesson, Stage, LESSONS};
+use crate::i18n::{self, tr};
+
+const PAGE: u16 = 10;
+
+pub fn run() -> io::Result<()> {
+    if !io::stdout().is_terminal() {
+        return Err(io::Error::other("--tui 需要在终端中运行"));
+    }
+    // 出错或 panic 时 ratatui 会恢复终端
+    let mut terminal = ratatui::try_init()?;
+    let result = App::load().run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+enum Row {
+    Stage(Stage),
+    Lesson(&'static dyn Lesson),
+}
+
+#[derive(PartialEq)]
+enum View {
+    Notes,
+    Output,
+}
+
+struct App {
+    rows: Vec<Row>,
+    state: ListState,
+    progress: Progress,
+    view: View,
+    // (课程编号, 输出)，只保留最近一次运行
+    output: Option<(&'static str, String)>,
+    scroll: u16,
+    // 右侧内容超出窗口的行数，绘制时按窗口大小更新
+    max_scroll: u16,
+    // 状态行的提示 (如进度文件读写失败)，代替快捷键说明显示
+    status: Option<String>,
+    quit: bool,
+}
+
+impl App {
+    fn load() -> Self {
+        match Progress::load() {
+            Ok(progress) => App::new(progress),
+            Err(e) => {
+                let mut app = App::new(Progress::default());
+                app.status = Some(tr!("⚠️ 无法读取学习进度: {}", e));
+                app
+            }
+        }
+    }
+
+    fn new(progress: Progress) -> Self {
+        let mut rows = Vec::new();
+        for stage in Stage::ALL {
+            rows.push(Row::Stage(stage));
+            for lesson in LESSONS.iter().copied().filter(|lesson| lesson.stage() == stage) {
+                rows.push(Row::Lesson(lesson));
+            }
+        }
+
+        // 默认选中下一门未完成的课程
+        let next = progress.next().map(|lesson| lesson.id());
+        let selected = rows
+            .iter()
+            .position(|row| matches!(row, Row::Lesson(lesson) if Some(lesson.id()) == next))
+            .or_else(|| rows.iter().position(|row| matches!(row, Row::Lesson(_))));
+
+        App {
+            rows,
+            state: ListState::default().with_selected(selected),
+            progress,
+            view: View::Notes,
+            output: None,
+            scroll: 0,
+            max_scroll: 0,
+            status: None,
+            quit: false,
+        }
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                self.on_key(key.code);
+            }
+        }
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<&'static dyn Lesson> {
+        match self.rows.get(self.state.selected()?) {
+            Some(Row::Lesson(lesson)) => Some(*lesson),
+            _ => None,
+        }
+    }
+
+    fn on_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.select(1),
+            KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(PAGE).min(self.max_scroll),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE),
+            KeyCode::Tab => {
+                self.view = match self.view {
+                    View::Notes => View::Output,
+                    View::Output => View::Notes,
+                };
+                self.scroll = 0;
+            }
+            KeyCode::Enter => self.run_selected(),
+            _ => {}
+        }
+    }
+
+    // 跳过阶段标题，只在课程之间移动
+    fn select(&mut self, step: isize) {
+        let mut index = self.state.selected().unwrap_or(0) as isize;
+        loop {
+            index += step;
+            match self.rows.get(index as usize) {
+                _ if index < 0 => return,
+                None => return,
+                Some(Row::Lesson(_)) => break,
+                Some(Row::Stage(_)) => continue,
+            }
+        }
+        self.state.select(Some(index as usize));
+        self.scroll = 0;
+    }
+
+    fn run_selected(&mut self) {
+        let Some(lesson) = self.selected() else {
+            return;
+        };
+        // 备用屏幕上不能写 stderr，进度读写的提示显示在状态行
+        let mut warnings = Vec::new();
+        let text = output::capture(Format::Plain, || {
+            super::run_tracked_with(lesson, &mut |warning| warnings.push(warning))
+        });
+        self.output = Some((lesson.id(), text));
+        match Progress::load() {
+            Ok(progress) => self.progress = progress,
+            Err(e) => warnings.push(tr!("⚠️ 无法读取学习进度: {}", e)),
+        }
+        self.status = (!warnings.is_empty()).then(|| warnings.join("  "));
+        self.view = View::Output;
+        self.scroll = 0;
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] = Layout::horizontal([Constraint::Length(30), Constraint::Min(0)]).areas(main);
+
+        let items: Vec<ListItem> = self.rows.iter().map(|row| self.list_item(row)).collect();
+        let done = self.progress.completed_count(LESSONS);
+        let list = List::new(items)
+            .block(Block::bordered().title(tr!(" 课程 {}/{} ", done, LESSONS.len())))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, left, &mut self.state);
+
+        let (title, text) = match self.view {
+            View::Notes => (i18n::lookup(" 说明 "), self.notes_text()),
+            View::Output => (i18n::lookup(" 输出 "), self.output_text()),
+        };
+        let pane = Paragraph::new(text)
+            .block(Block::bordered().title(title))
+            .wrap(Wrap { trim: false });
+        // line_count 包含上下边框，窗口高度同样包含
+        let height = pane.line_count(right.width.saturating_sub(2));
+        self.max_scroll = u16::try_from(height).unwrap_or(u16::MAX).saturating_sub(right.height);
+        self.scroll = self.scroll.min(self.max_scroll);
+        frame.render_widget(pane.scroll((self.scroll, 0)), right);
+
+        let footer_line = match &self.status {
+            Some(status) => Line::from(status.clone()).yellow(),
+            None => Line::from(i18n::lookup("↑/↓ 选择  Enter 运行  Tab 说明/输出  PgUp/PgDn 滚动  q 退出")).dark_gray(),
+        };
+        frame.render_widget(footer_line, footer);
+    }
+
+    fn list_item(&self, row: &Row) -> ListItem<'static> {
+        match row {
+            Row::Stage(stage) => ListItem::new(format!("{} {}", stage.icon(), stage.name())).bold(),
+            Row::Lesson(lesson) => {
+                let mark = match self.progress.status(lesson.id()) {
+                    Some(Status::Completed) => "✅",
+                    Some(Status::Started) => "🔄",
+                    None => "⬜",
+                };
+                ListItem::new(format!("  {} {} {}", mark, lesson.id(), lesson.title()))
+            }
+        }
+    }
+
+    fn notes_text(&self) -> Text<'static> {
+        let Some(lesson) = self.selected() else {
+            return Text::default();
+        };
+        let status = match self.progress.lessons.get(lesson.id()) {
+            Some(record) if record.status == Status::Completed => tr!("已完成，运行 {} 次", record.runs),
+            Some(_) => i18n::lookup("未运行完").to_string(),
+            None => i18n::lookup("未开始").to_string(),
+        };
+
+        let mut lines = vec![
+            Line::from(tr!("第{}课：{}", lesson.id(), lesson.title())).bold().fg(Color::Green),
+            Line::from(format!("{} {}", lesson.stage().icon(), lesson.stage())),
+            Line::from(tr!("要点：{}", lesson.summary())),
+            Line::from(tr!("进度：{}", status)),
+            Line::from(""),
+        ];
+        match lesson.notes() {
+            Some(notes) => lines.extend(notes.lines().map(|line| Line::from(line.to_string()))),
+            None => lines.push(Line::from(i18n::lookup("本课没有语言对比说明"))),
+        }
+        Text::from(lines)
+    }
+
+    fn output_text(&self) -> Text<'static> {
+        let selected = self.selected().map(|lesson| lesson.id());
+        match &self.output {
+            Some((id, text)) if Some(*id) == selected => Text::from(text.clone()),
+            _ => Text::from(i18n::lookup("按 Enter 运行本课，输出显示在这里")).dark_gray(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    use super::*;
+
+    fn draw(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    fn selected_id(app: &App) -> Option<&'static str> {
+        app.selected().map(|lesson| lesson.id())
+    }
+
+    #[test]
+    fn starts_on_the_next_unfinished_lesson() {
+        let mut progress = Progress::default();
+        progress.start(LESSONS[0].id());
+        progress.complete(LESSONS[0].id());
+        assert_eq!(selected_id(&App::new(progress)), Some(LESSONS[1].id()));
+    }
+
+    #[test]
+    fn selection_skips_stage_rows_and_stops_at_the_ends() {
+        let mut app = App::new(Progress::default());
+        assert_eq!(selected_id(&app), Some(LESSONS[0].id()));
+        app.on_key(KeyCode::Up);
+        assert_eq!(selected_id(&app), Some(LESSONS[0].id()));
+
+        // 每一步都落在课程上，按编号顺序经过所有课程
+        let mut visited = vec![selected_id(&app).unwrap()];
+        for _ in 0..LESSONS.len() + 5 {
+            app.on_key(KeyCode::Char('j'));
+            if visited.last() != selected_id(&app).as_ref() {
+                visited.push(selected_id(&app).unwrap());
+            }
+        }
+        let ids: Vec<&str> = LESSONS.iter().map(|lesson| lesson.id()).collect();
+        assert_eq!(visited, ids);
+
+        app.on_key(KeyCode::Char('k'));
+        assert_eq!(selected_id(&app), Some(ids[ids.len() - 2]));
+    }
+
+    #[test]
+    fn tab_switches_view_and_resets_scroll() {
+        let mut app = App::new(Progress::default());
+        app.max_scroll = 100;
+        app.on_key(KeyCode::PageDown);
+        assert_eq!(app.scroll, PAGE);
+        app.on_key(KeyCode::Tab);
+        assert!(app.view == View::Output && app.scroll == 0);
+        app.on_key(KeyCode::Tab);
+        assert!(app.view == View::Notes);
+    }
+
+    #[test]
+    fn scroll_stops_at_the_end_of_the_output() {
+        let mut app = App::new(Progress::default());
+        let id = selected_id(&app).unwrap();
+        let text: Vec<String> = (1..=50).map(|line| format!("line {}", line)).collect();
+        app.output = Some((id, text.join("\n")));
+        app.view = View::Output;
+
+        draw(&mut app);
+        // 50 行内容；右侧窗口高 19 行 (终端 20 行减去状态行)，去掉边框后可见 17 行
+        assert_eq!(app.max_scroll, 50 - 17);
+        for _ in 0..10 {
+            app.on_key(KeyCode::PageDown);
+        }
+        assert_eq!(app.scroll, app.max_scroll);
+        assert!(draw(&mut app).contains("line 50"));
+
+        app.on_key(KeyCode::PageUp);
+        assert_eq!(app.scroll, app.max_scroll - PAGE);
+    }
+
+    #[test]
+    fn status_replaces_the_key_help() {
+        let mut app = App::new(Progress::default());
+        assert!(draw(&mut app).contains("Enter"));
+        app.status = Some("⚠️ progress.json".to_string());
+        let screen = draw(&mut app);
+        assert!(screen.contains("progress.json") && !screen.contains("Enter"), "{}", screen);
+    }
+
+    #[test]
+    fn q_and_esc_quit() {
+        for code in [KeyCode::Char('q'), KeyCode::Esc] {
+            let mut app = App::new(Progress::default());
+            app.on_key(code);
+            assert!(app.quit);
+        }
+    }
+}
@@ -2,176 +2,164 @@
 // 基于 C/Python/TypeScript 背景的系统化学习路径
 
 mod apps;
+mod cli;
+mod db;
+mod error;
+mod i18n;
 mod learn;
+mod openapi;
+mod server;
+mod settings;
+mod validation;
 
-use std::env;
+use std::process::ExitCode;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    if args.len() < 2 {
-        show_help();
-        return;
+use clap::FromArgMatches;
+
+use cli::{Cli, Command, LearnAction, LearnArgs, EXIT_FAILURE, EXIT_USAGE};
+use learn::output::{self, Output};
+use learn::progress::Progress;
+
+fn main() -> ExitCode {
+    // 帮助文本在解析时输出，需要先确定语言
+    let args: Vec<_> = std::env::args_os().collect();
+    i18n::set(i18n::Lang::from_args(&args).unwrap_or_else(i18n::Lang::detect));
+    let cli = match Cli::from_arg_matches(&cli::command().get_matches_from(args)) {
+        Ok(cli) => cli,
+        Err(e) => e.exit(),
+    };
+    if let Some(lang) = cli.lang {
+        i18n::set(lang);
     }
-    
-    match args[1].as_str() {
-        "learn" => {
-            if args.len() >= 3 {
-                run_lesson(&args[2]);
-            } else {
-                show_lessons();
+
+    match cli.command {
+        Command::Learn(args) => run_learn(args),
+        Command::Web(args) => {
+            let overrides = match args.into_overrides() {
+                Ok(overrides) => overrides,
+                Err(e) => return cli::fail(EXIT_USAGE, &e),
+            };
+            let settings = match settings::Settings::load(&cli.config, &overrides) {
+                Ok(settings) => settings,
+                Err(e) => return cli::config_error(e),
+            };
+            server::init_tracing(cli.log_level.as_deref(), &settings.log.level);
+
+            let addr = settings.server.addr();
+            match server::run(settings) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => cli::fail(EXIT_FAILURE, &i18n::tr!("Web 服务器启动失败 ({}): {}", addr, e)),
             }
+        }
+        Command::Openapi { format, out } => {
+            // 未指定格式时按输出文件扩展名推断
+            let format = format.unwrap_or_else(|| {
+                out.as_deref()
+                    .map_or(openapi::Format::Json, openapi::Format::from_path)
+            });
+            if let Err(e) = openapi::export(format, out.as_deref()) {
+                return cli::fail(EXIT_FAILURE, &i18n::tr!("导出 OpenAPI 文档失败: {}", e));
+            }
+            if let Some(path) = out {
+                eprintln!("{}", i18n::tr!("✅ OpenAPI 文档已写入 {}", path));
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Completions { shell } => {
+            cli::print_completions(shell);
+            ExitCode::SUCCESS
+        }
+        Command::Manpage => match cli::print_manpage() {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => cli::fail(EXIT_FAILURE, &i18n::tr!("生成 man 手册失败: {}", e)),
         },
-        "web" => {
-            println!("启动 Web 服务器...");
-            println!("提示：要运行 Web 服务器，请安装 tokio 并修改 main 函数为异步");
-            println!("参考 learn::_88_web_server 模块中的完整示例");
-        },
-        "help" | "-h" | "--help" => show_help(),
-        _ => {
-            println!("未知命令: {}", args[1]);
-            show_help();
+        Command::Migrate { overrides } => {
+            let settings = match settings::Settings::load(&cli.config, &overrides) {
+                Ok(settings) => settings,
+                Err(e) => return cli::config_error(e),
+            };
+            server::init_tracing(cli.log_level.as_deref(), &settings.log.level);
+
+            match server::migrate(settings) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => cli::fail(EXIT_FAILURE, &i18n::tr!("数据库迁移失败: {}", e)),
+            }
         }
     }
 }
 
-fn show_help() {
-    println!("Rust 学习程序");
-    println!();
-    println!("用法:");
-    println!("  cargo run learn [课程编号]  - 运行指定课程");
-    println!("  cargo run learn           - 显示所有课程");
-    println!("  cargo run web             - 启动 Web 服务器");
-    println!("  cargo run help            - 显示帮助");
-    println!();
-    println!("示例:");
-    println!("  cargo run learn 01        - 运行第1课：变量与类型");
-    println!("  cargo run learn 21        - 运行第21课：所有权基础");
-    println!("  cargo run learn 88        - 运行第88课：Web 服务器");
-}
+fn run_learn(args: LearnArgs) -> ExitCode {
+    if args.checklist {
+        print!("{}", learn::checklist());
+        return ExitCode::SUCCESS;
+    }
+    if args.tui {
+        return match learn::tui::run() {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => cli::fail(EXIT_FAILURE, &i18n::tr!("无法打开终端界面: {}", e)),
+        };
+    }
 
-fn show_lessons() {
-    println!("=== Rust 学习路径 ===");
-    println!();
-    
-    println!("📚 阶段一：基础语法 (01-20)");
-    println!("  01 - 变量与类型           - let, mut, 类型推断");
-    println!("  02 - 常量与静态变量       - const, static");
-    println!("  03 - 基本操作符           - 算术、比较、逻辑操作");
-    println!("  04 - 控制流               - if, loop, while, for, match");
-    println!("  05 - 函数                 - fn, 参数, 返回值, 闭包");
-    println!("  06-20 - 其他基础概念      - (待实现)");
-    println!();
-    
-    println!("🔐 阶段二：所有权系统 (21-40)");
-    println!("  21 - 所有权基础           - 移动语义, 克隆, 栈vs堆");
-    println!("  23 - 借用与引用           - &T, &mut T, 借用规则");
-    println!("  24-40 - 其他所有权概念    - (待实现)");
-    println!();
-    
-    println!("🏗️ 阶段三：数据结构 (41-60)");
-    println!("  41 - 结构体               - struct, impl, 方法");
-    println!("  42 - 枚举                 - enum, match, Option, Result");
-    println!("  43-60 - 其他数据结构      - (待实现)");
-    println!();
-    
-    println!("⚡ 阶段四：高级特性 (61-80)");
-    println!("  61 - 错误处理             - Result, ?, panic!, 自定义错误");
-    println!("  62-80 - 其他高级特性      - (待实现)");
-    println!();
-    
-    println!("🌐 阶段五：实用开发 (81-99)");
-    println!("  84 - 异步基础             - async/await, Future, 异步概念");
-    println!("  88 - Web 服务器           - Axum 框架, HTTP 服务");
-    println!("  89-99 - 其他实用技能      - (待实现)");
-    println!();
-    
-    println!("使用方法:");
-    println!("  cargo run learn 01   # 运行第1课");
-    println!("  cargo run learn 21   # 运行第21课");
-    println!("  cargo run learn 88   # 运行第88课");
-    println!();
-    println!("💡 提示：每个课程都包含详细的对比说明和可运行的示例代码");
-}
+    if args.serve {
+        let id = args.id.unwrap_or_default();
+        let Some(serve) = learn::find(&id).and_then(learn::server) else {
+            return cli::fail(EXIT_USAGE, &i18n::tr!("第{}课没有可以启动的服务器", id));
+        };
+        return match serve() {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => cli::fail(EXIT_FAILURE, &i18n::tr!("示例服务器启动失败: {}", e)),
+        };
+    }
 
-fn run_lesson(lesson: &str) {
-    match lesson {
-        "01" => {
-            println!("🦀 第01课：变量与类型");
-            learn::_01_variables_and_types::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_01_variables_and_types::main_example();
+    let stdout = |format: Option<output::Format>| Output::stdout(format.unwrap_or_else(output::Format::detect));
+    match (args.action, args.id) {
+        (Some(LearnAction::Progress { format }), _) => match Progress::load() {
+            Ok(progress) => output::render(stdout(format), || progress.show()),
+            Err(e) => return cli::fail(EXIT_FAILURE, &i18n::tr!("无法读取学习进度: {}", e)),
         },
-        "02" => {
-            println!("🦀 第02课：常量与静态变量");
-            learn::_02_constants_and_static::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_02_constants_and_static::main_example();
-        },
-        "03" => {
-            println!("🦀 第03课：基本操作符");
-            learn::_03_basic_operators::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_03_basic_operators::main_example();
-        },
-        "04" => {
-            println!("🦀 第04课：控制流");
-            learn::_04_control_flow::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_04_control_flow::main_example();
-        },
-        "05" => {
-            println!("🦀 第05课：函数");
-            learn::_05_functions::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_05_functions::main_example();
-        },
-        "21" => {
-            println!("🦀 第21课：所有权基础");
-            learn::_21_ownership_basics::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_21_ownership_basics::main_example();
-        },
-        "23" => {
-            println!("🦀 第23课：借用与引用");
-            learn::_23_borrowing::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_23_borrowing::main_example();
-        },
-        "41" => {
-            println!("🦀 第41课：结构体");
-            learn::_41_structs::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_41_structs::main_example();
-        },
-        "42" => {
-            println!("🦀 第42课：枚举");
-            learn::_42_enums::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_42_enums::main_example();
-        },
-        "61" => {
-            println!("🦀 第61课：错误处理");
-            learn::_61_error_handling::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_61_error_handling::main_example();
+        (Some(LearnAction::Next { format }), _) => {
+            let progress = match Progress::load() {
+                Ok(progress) => progress,
+                Err(e) => return cli::fail(EXIT_FAILURE, &i18n::tr!("无法读取学习进度: {}", e)),
+            };
+            match progress.next() {
+                Some(lesson) => output::render(stdout(format), || learn::run_tracked(lesson)),
+                None => println!("{}", i18n::lookup("🎉 所有课程都已完成，运行 'cargo run learn reset' 可以重新开始")),
+            }
+        }
+        (Some(LearnAction::Exercise { id, reset }), _) => {
+            // 编号已由命令行校验，这里只是防御
+            let Some(lesson) = learn::find(&id) else {
+                return unknown_lesson(&id);
+            };
+            if lesson.exercises().is_empty() {
+                return cli::fail(EXIT_USAGE, &i18n::tr!("第{}课还没有练习", id));
+            }
+            return match learn::exercise::run(lesson, reset) {
+                Ok(true) => ExitCode::SUCCESS,
+                Ok(false) => ExitCode::from(EXIT_FAILURE),
+                Err(e) => cli::fail(EXIT_FAILURE, &i18n::tr!("练习运行失败: {}", e)),
+            };
+        }
+        (Some(LearnAction::Reset), _) => match Progress::reset() {
+            Ok(true) => println!("{}", i18n::lookup("✅ 学习进度已清除")),
+            Ok(false) => println!("{}", i18n::lookup("还没有学习进度")),
+            Err(e) => return cli::fail(EXIT_FAILURE, &i18n::tr!("无法清除学习进度: {}", e)),
         },
-        "84" => {
-            println!("🦀 第84课：异步基础");
-            learn::_84_async_basics::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_84_async_basics::main_example();
+        (Some(LearnAction::Export { format, out }), _) => match learn::book::export(format, &out) {
+            Ok(count) => eprintln!("{}", i18n::tr!("✅ 已导出 {} 课到 {}", count, out.display())),
+            Err(e) => return cli::fail(EXIT_FAILURE, &i18n::tr!("无法导出课程手册 {}: {}", out.display(), e)),
         },
-        "88" => {
-            println!("🦀 第88课：Web 服务器");
-            learn::_88_web_server::run();
-            println!("\n--- 可运行示例 ---");
-            learn::_88_web_server::main_example();
+        (None, Some(id)) => match learn::find(&id) {
+            Some(lesson) => output::render(stdout(args.format), || learn::run_tracked(lesson)),
+            None => return unknown_lesson(&id),
         },
-        _ => {
-            println!("❌ 课程 {} 暂未实现", lesson);
-            println!("可用课程: 01, 02, 03, 04, 05, 21, 23, 41, 42, 61, 84, 88");
-            println!("运行 'cargo run learn' 查看完整课程列表");
-        }
+        (None, None) => output::render(stdout(args.format), learn::show_lessons),
     }
-}
\ No newline at end of file
+    ExitCode::SUCCESS
+}
+
+fn unknown_lesson(id: &str) -> ExitCode {
+    let ids = learn::ids().join(", ");
+    cli::fail(EXIT_USAGE, &i18n::tr!("课程 {} 暂未实现，可用课程: {}", id, ids))
+}
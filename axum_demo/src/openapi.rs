@@ -0,0 +1,55 @@
+// 导出 OpenAPI 文档，不启动服务器
+// 供前端 (nextjs-hono) 和客户端代码生成使用
+
+use std::{fs, io};
+
+use crate::apps;
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Format {
+    Json,
+    #[value(alias = "yml")]
+    Yaml,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "yaml" | "yml" => Some(Format::Yaml),
+            _ => None,
+        }
+    }
+
+    // 根据输出文件扩展名推断格式，默认 JSON
+    pub fn from_path(path: &str) -> Self {
+        match path.rsplit_once('.') {
+            Some((_, ext)) => Format::parse(ext).unwrap_or(Format::Json),
+            None => Format::Json,
+        }
+    }
+}
+
+pub fn render(format: Format) -> io::Result<String> {
+    let openapi = apps::openapi();
+    let mut text = match format {
+        Format::Json => openapi.to_pretty_json().map_err(io::Error::other)?,
+        Format::Yaml => openapi.to_yaml().map_err(io::Error::other)?,
+    };
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+// out 为 None 时写到标准输出
+pub fn export(format: Format, out: Option<&str>) -> io::Result<()> {
+    let text = render(format)?;
+    match out {
+        Some(path) => fs::write(path, text),
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// Web 服务器启动
+// 创建 tokio 运行时，绑定配置中的地址并提供 apps 路由
+
+use axum::http::HeaderValue;
+use tokio::net::TcpListener;
+use tower_http::cors::{Any, CorsLayer};
+use tracing_subscriber::EnvFilter;
+
+use crate::apps;
+use crate::db::Database;
+use crate::settings::{CorsSettings, Settings};
+
+// 同步入口：main 不是 async，这里手动创建运行时
+pub fn run(settings: Settings) -> std::io::Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(serve(settings))
+}
+
+// 只建表不启动服务器，供部署脚本在启动前执行
+pub fn migrate(settings: Settings) -> std::io::Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let db = Database::connect(&settings.database.url)
+            .await
+            .map_err(std::io::Error::other)?;
+        apps::migrate(&db).await.map_err(std::io::Error::other)?;
+        tracing::info!(backend = db.backend(), "数据表已就绪");
+        Ok(())
+    })
+}
+
+pub async fn serve(settings: Settings) -> std::io::Result<()> {
+    let db = Database::connect(&settings.database.url)
+        .await
+        .map_err(std::io::Error::other)?;
+    apps::migrate(&db).await.map_err(std::io::Error::other)?;
+    tracing::info!(backend = db.backend(), "数据库已连接");
+
+    let app = apps::create_router(&db, &settings.auth).layer(cors_layer(&settings.cors));
+
+    let listener = TcpListener::bind(settings.server.addr()).await?;
+    tracing::info!(env = %settings.env, "🚀 服务器启动在 http://{}", listener.local_addr()?);
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    tracing::info!("服务器已关闭");
+    Ok(())
+}
+
+// 优先级: 命令行 --log-level > RUST_LOG > 配置中的 log.level
+pub fn init_tracing(cli_level: Option<&str>, config_level: &str) {
+    let filter = match cli_level {
+        Some(level) => EnvFilter::new(level),
+        None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(config_level)),
+    };
+    tracing_subscriber::fmt().with_env_filter(filter).init();
+}
+
+fn cors_layer(cors: &CorsSettings) -> CorsLayer {
+    let layer = CorsLayer::new().allow_methods(Any).allow_headers(Any);
+
+    if cors.allowed_origins.iter().any(|origin| origin == "*") {
+        return layer.allow_origin(Any);
+    }
+
+    // 来源格式已在加载配置时校验
+    let origins: Vec<HeaderValue> = cors
+        .allowed_origins
+        .iter()
+        .filter_map(|origin| origin.parse().ok())
+        .collect();
+    layer.allow_origin(origins)
+}
+
+// Ctrl+C 或 SIGTERM 时优雅关闭
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("无法监听 Ctrl+C 信号");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("无法监听 SIGTERM 信号")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("收到关闭信号，正在停止服务器...");
+}
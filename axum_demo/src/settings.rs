@@ -0,0 +1,533 @@
+// 应用配置
+// 分层加载，后面的来源覆盖前面的：
+//   1. config/default.yaml          - 默认值（必须存在）
+//   2. config/{APP_ENV}.yaml        - 环境覆盖，APP_ENV 默认 development
+//   3. config/local.yaml            - 本地覆盖（不提交到仓库）
+//   4. APP_ 前缀环境变量 / .env     - 如 APP_SERVER__PORT=3000
+//   5. DATABASE_URL                 - 与 .env.example 保持一致
+//   6. 命令行覆盖                   - 如 --set server.port=3000
+// 配置文件和命令行覆盖中的未知配置项 (多半是拼写错误) 视为错误；
+// 环境变量中可能有其他程序的 APP_ 变量，不做检查
+
+use std::{collections::HashMap, fmt};
+
+use config::{Config, Environment, File};
+use serde::de::DeserializeOwned;
+
+use crate::i18n::{self, tr};
+
+pub const DEFAULT_CONFIG_DIR: &str = "config";
+
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub env: String,
+    pub server: ServerSettings,
+    pub database: DatabaseSettings,
+    pub log: LogSettings,
+    pub cors: CorsSettings,
+    pub auth: AuthSettings,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerSettings {
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct DatabaseSettings {
+    pub url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogSettings {
+    pub level: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CorsSettings {
+    pub allowed_origins: Vec<String>,
+}
+
+// Default 仅用于不加载配置时生成 OpenAPI 文档
+#[derive(Debug, Clone, Default)]
+pub struct AuthSettings {
+    // HS256 签名密钥
+    pub jwt_secret: String,
+    pub token_ttl_secs: u64,
+    pub issuer: String,
+    pub audience: String,
+}
+
+impl ServerSettings {
+    pub fn addr(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+// 单个配置项的问题，message 已按当前语言生成
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub key: String,
+    pub message: String,
+}
+
+// 加载失败时一次性列出所有无效的配置项
+#[derive(Debug)]
+pub struct SettingsError {
+    pub problems: Vec<Problem>,
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", tr!("配置无效 ({} 项):", self.problems.len()))?;
+        for problem in &self.problems {
+            write!(f, "\n  - {}: {}", problem.key, problem.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+impl SettingsError {
+    fn single(key: &str, message: impl ToString) -> Self {
+        SettingsError {
+            problems: vec![Problem {
+                key: key.to_string(),
+                message: message.to_string(),
+            }],
+        }
+    }
+}
+
+pub const LOG_LEVELS: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+const DATABASE_SCHEMES: [&str; 4] = ["postgres://", "postgresql://", "sqlite:", "memory:"];
+const MIN_SECRET_LEN: usize = 32;
+
+impl Settings {
+    // 从 config 目录加载，overrides 为命令行传入的 (key, value)
+    pub fn load(config_dir: &str, overrides: &[(String, String)]) -> Result<Self, SettingsError> {
+        // .env 不存在时忽略
+        dotenvy::dotenv().ok();
+
+        // 非 UTF-8 的环境变量与配置无关，直接跳过
+        let vars = std::env::vars_os()
+            .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)))
+            .collect();
+        Self::load_from(config_dir, vars, overrides)
+    }
+
+    // vars 为环境变量，与进程环境分开以便测试
+    fn load_from(
+        config_dir: &str,
+        vars: HashMap<String, String>,
+        overrides: &[(String, String)],
+    ) -> Result<Self, SettingsError> {
+        let env = vars.get("APP_ENV").cloned().unwrap_or_else(|| "development".to_string());
+        let database_url = vars.get("DATABASE_URL").cloned();
+
+        let files = [
+            File::with_name(&format!("{}/default", config_dir)),
+            File::with_name(&format!("{}/{}", config_dir, env)).required(false),
+            File::with_name(&format!("{}/local", config_dir)).required(false),
+        ];
+        let file_config = Config::builder()
+            .add_source(files.to_vec())
+            .build()
+            .map_err(|e| SettingsError::single(config_dir, e))?;
+        let mut checked_keys = Vec::new();
+        let table = file_config.try_deserialize().map_err(|e| SettingsError::single(config_dir, e))?;
+        collect_keys(table, "", &mut checked_keys);
+        checked_keys.extend(overrides.iter().map(|(key, _)| key.clone()));
+
+        let mut builder = Config::builder()
+            .add_source(files.to_vec())
+            .add_source(
+                Environment::with_prefix("APP")
+                    .prefix_separator("_")
+                    .separator("__")
+                    .list_separator(",")
+                    .with_list_parse_key("cors.allowed_origins")
+                    .try_parsing(true)
+                    .source(Some(vars)),
+            )
+            .set_override_option("database.url", database_url)
+            .map_err(|e| SettingsError::single("database.url", e))?;
+
+        for (key, value) in overrides {
+            builder = builder
+                .set_override(key.as_str(), value.as_str())
+                .map_err(|e| SettingsError::single(key, e))?;
+        }
+
+        let config = builder
+            .build()
+            .map_err(|e| SettingsError::single(config_dir, e))?;
+
+        Self::from_config(env, &config, &checked_keys)
+    }
+
+    // keys 为需要确认是已知配置项的键
+    fn from_config(env: String, config: &Config, keys: &[String]) -> Result<Self, SettingsError> {
+        let mut reader = Reader {
+            config,
+            read: Vec::new(),
+            problems: Vec::new(),
+        };
+
+        let host: Option<String> = reader.get("server.host");
+        let port: Option<u16> = reader.get("server.port");
+        let database_url: Option<String> = reader.get("database.url");
+        let log_level: Option<String> = reader.get("log.level");
+        let allowed_origins: Option<Vec<String>> = reader.get("cors.allowed_origins");
+        let jwt_secret: Option<String> = reader.get("auth.jwt_secret");
+        let token_ttl_secs: Option<u64> = reader.get("auth.token_ttl_secs");
+        let issuer: Option<String> = reader.get("auth.issuer");
+        let audience: Option<String> = reader.get("auth.audience");
+
+        for key in keys {
+            let known = reader.read.iter().any(|read| read == key);
+            reader.check(known, key, i18n::lookup("未知的配置项"));
+        }
+
+        // 类型正确后再检查取值
+        if let Some(host) = &host {
+            reader.check(!host.trim().is_empty(), "server.host", i18n::lookup("不能为空"));
+        }
+        if let Some(port) = port {
+            reader.check(port != 0, "server.port", i18n::lookup("不能为 0"));
+        }
+        if let Some(url) = &database_url {
+            reader.check(
+                DATABASE_SCHEMES.iter().any(|scheme| url.starts_with(scheme)),
+                "database.url",
+                // 地址中可能带有用户名和密码，只显示协议部分
+                tr!("不支持的数据库类型 \"{}\"，地址应以 {} 开头", scheme(url), DATABASE_SCHEMES.join(" / ")),
+            );
+        }
+        if let Some(level) = &log_level {
+            reader.check(
+                LOG_LEVELS.contains(&level.to_lowercase().as_str()),
+                "log.level",
+                tr!("无效的日志级别 {}，可选: {}", level, LOG_LEVELS.join(", ")),
+            );
+        }
+        if let Some(origins) = &allowed_origins {
+            for origin in origins {
+                reader.check(
+                    origin == "*" || origin.starts_with("http://") || origin.starts_with("https://"),
+                    "cors.allowed_origins",
+                    tr!("无效的来源 {}，应为 * 或 http(s)://...", origin),
+                );
+            }
+        }
+        if let Some(secret) = &jwt_secret {
+            reader.check(
+                secret.len() >= MIN_SECRET_LEN,
+                "auth.jwt_secret",
+                tr!("长度至少 {} 字节", MIN_SECRET_LEN),
+            );
+        }
+        if let Some(ttl) = token_ttl_secs {
+            reader.check(ttl > 0, "auth.token_ttl_secs", i18n::lookup("必须大于 0"));
+        }
+        if let Some(issuer) = &issuer {
+            reader.check(!issuer.trim().is_empty(), "auth.issuer", i18n::lookup("不能为空"));
+        }
+        if let Some(audience) = &audience {
+            reader.check(!audience.trim().is_empty(), "auth.audience", i18n::lookup("不能为空"));
+        }
+
+        if !reader.problems.is_empty() {
+            return Err(SettingsError {
+                problems: reader.problems,
+            });
+        }
+
+        // 走到这里所有字段都已成功读取
+        Ok(Settings {
+            env,
+            server: ServerSettings {
+                host: host.unwrap(),
+                port: port.unwrap(),
+            },
+            database: DatabaseSettings {
+                url: database_url.unwrap(),
+            },
+            log: LogSettings {
+                level: log_level.unwrap().to_lowercase(),
+            },
+            cors: CorsSettings {
+                allowed_origins: allowed_origins.unwrap(),
+            },
+            auth: AuthSettings {
+                jwt_secret: jwt_secret.unwrap(),
+                token_ttl_secs: token_ttl_secs.unwrap(),
+                issuer: issuer.unwrap(),
+                audience: audience.unwrap(),
+            },
+        })
+    }
+}
+
+// 地址开头的协议名，如 postgres；没有合法的协议名时为空
+fn scheme(url: &str) -> &str {
+    let scheme = url.split_once(':').map_or("", |(scheme, _)| scheme);
+    let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+    if valid { scheme } else { "" }
+}
+
+// 展开嵌套的表，得到 server.port 形式的键；列表作为一个值
+fn collect_keys(table: config::Map<String, config::Value>, prefix: &str, keys: &mut Vec<String>) {
+    for (key, value) in table {
+        let key = format!("{}{}", prefix, key);
+        match value.kind {
+            config::ValueKind::Table(table) => collect_keys(table, &format!("{}.", key), keys),
+            _ => keys.push(key),
+        }
+    }
+}
+
+// 逐项读取配置，收集所有错误而不是遇到第一个就返回
+struct Reader<'a> {
+    config: &'a Config,
+    // 已读取的键，其余的都是未知配置项
+    read: Vec<String>,
+    problems: Vec<Problem>,
+}
+
+impl Reader<'_> {
+    fn get<T: DeserializeOwned>(&mut self, key: &str) -> Option<T> {
+        self.read.push(key.to_string());
+        match self.config.get::<T>(key) {
+            Ok(value) => Some(value),
+            Err(config::ConfigError::NotFound(_)) => {
+                self.push(key, i18n::lookup("缺少配置项"));
+                None
+            }
+            Err(e) => {
+                self.push(key, e);
+                None
+            }
+        }
+    }
+
+    fn check(&mut self, ok: bool, key: &str, message: impl ToString) {
+        if !ok {
+            self.push(key, message);
+        }
+    }
+
+    fn push(&mut self, key: &str, message: impl ToString) {
+        self.problems.push(Problem {
+            key: key.to_string(),
+            message: message.to_string(),
+        });
+    }
+}
+
+// 解析命令行的 key=value 覆盖
+pub fn parse_override(arg: &str) -> Option<(String, String)> {
+    let (key, value) = arg.split_once('=')?;
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+    Some((key.to_string(), value.to_string()))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+
+    const DEFAULT_YAML: &str = "
+server:
+  host: 127.0.0.1
+  port: 8001
+database:
+  url: memory://
+log:
+  level: info
+cors:
+  allowed_origins: [http://localhost:3000]
+auth:
+  jwt_secret: test-secret-with-at-least-32-bytes
+  token_ttl_secs: 60
+  issuer: axum_demo
+  audience: axum_demo-api
+";
+
+    // 每个测试使用自己的配置目录，files 为 (文件名, 内容)
+    fn config_dir(name: &str, files: &[(&str, &str)]) -> String {
+        let dir: PathBuf = std::env::temp_dir().join("axum_demo-settings-tests").join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("default.yaml"), DEFAULT_YAML).unwrap();
+        for (file, content) in files {
+            fs::write(dir.join(file), content).unwrap();
+        }
+        dir.to_string_lossy().into_owned()
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn set(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    fn problem_keys(error: &SettingsError) -> Vec<&str> {
+        error.problems.iter().map(|p| p.key.as_str()).collect()
+    }
+
+    #[test]
+    fn later_sources_override_earlier_ones() {
+        let dir = config_dir(
+            "precedence",
+            &[
+                ("development.yaml", "server:\n  port: 8002\nlog:\n  level: debug\n"),
+                ("staging.yaml", "server:\n  port: 8003\n"),
+            ],
+        );
+
+        // 没有对应的环境文件时只使用 default
+        let settings = Settings::load_from(&dir, vars(&[("APP_ENV", "production")]), &[]).unwrap();
+        assert_eq!(settings.env, "production");
+        assert_eq!((settings.server.port, settings.log.level.as_str()), (8001, "info"));
+
+        // APP_ENV 默认 development
+        let settings = Settings::load_from(&dir, vars(&[]), &[]).unwrap();
+        assert_eq!(settings.env, "development");
+        assert_eq!((settings.server.port, settings.log.level.as_str()), (8002, "debug"));
+
+        let settings = Settings::load_from(&dir, vars(&[("APP_ENV", "staging")]), &[]).unwrap();
+        assert_eq!(settings.server.port, 8003);
+
+        // local 覆盖环境文件
+        fs::write(PathBuf::from(&dir).join("local.yaml"), "server:\n  port: 8004\n").unwrap();
+        let settings = Settings::load_from(&dir, vars(&[("APP_ENV", "staging")]), &[]).unwrap();
+        assert_eq!(settings.server.port, 8004);
+
+        // APP_ 环境变量覆盖 local
+        let env = vars(&[("APP_ENV", "staging"), ("APP_SERVER__PORT", "8005")]);
+        let settings = Settings::load_from(&dir, env.clone(), &[]).unwrap();
+        assert_eq!(settings.server.port, 8005);
+
+        // --set 覆盖所有来源
+        let settings = Settings::load_from(&dir, env, &[set("server.port", "8006")]).unwrap();
+        assert_eq!(settings.server.port, 8006);
+    }
+
+    #[test]
+    fn database_url_overrides_app_vars_and_set_overrides_it() {
+        let dir = config_dir("database-url", &[]);
+        let env = vars(&[
+            ("APP_DATABASE__URL", "sqlite://app.db"),
+            ("DATABASE_URL", "sqlite://env.db"),
+        ]);
+
+        let settings = Settings::load_from(&dir, env.clone(), &[]).unwrap();
+        assert_eq!(settings.database.url, "sqlite://env.db");
+
+        let overrides = [set("database.url", "memory://")];
+        let settings = Settings::load_from(&dir, env, &overrides).unwrap();
+        assert_eq!(settings.database.url, "memory://");
+    }
+
+    #[test]
+    fn env_var_lists_are_split() {
+        let dir = config_dir("cors-list", &[]);
+        let env = vars(&[("APP_CORS__ALLOWED_ORIGINS", "http://a.com,http://b.com")]);
+        let settings = Settings::load_from(&dir, env, &[]).unwrap();
+        assert_eq!(settings.cors.allowed_origins, ["http://a.com", "http://b.com"]);
+    }
+
+    #[test]
+    fn all_invalid_keys_are_reported_together() {
+        let dir = config_dir("invalid", &[]);
+        let overrides = [
+            set("server.port", "0"),
+            set("log.level", "loud"),
+            set("auth.jwt_secret", "short"),
+            set("auth.issuer", " "),
+        ];
+        let error = Settings::load_from(&dir, vars(&[]), &overrides).unwrap_err();
+        assert_eq!(
+            problem_keys(&error),
+            ["server.port", "log.level", "auth.jwt_secret", "auth.issuer"]
+        );
+    }
+
+    #[test]
+    fn type_errors_and_missing_keys_are_reported_together() {
+        let dir = config_dir("types", &[("development.yaml", "auth:\n  issuer: null\n")]);
+        let overrides = [set("server.port", "not-a-port"), set("auth.token_ttl_secs", "-1")];
+        let error = Settings::load_from(&dir, vars(&[]), &overrides).unwrap_err();
+        assert_eq!(
+            problem_keys(&error),
+            ["server.port", "auth.token_ttl_secs", "auth.issuer"]
+        );
+    }
+
+    #[test]
+    fn unknown_database_scheme_is_rejected() {
+        let dir = config_dir("scheme", &[]);
+        let env = vars(&[("DATABASE_URL", "mysql://root@localhost/notes")]);
+        let error = Settings::load_from(&dir, env, &[]).unwrap_err();
+        assert_eq!(problem_keys(&error), ["database.url"]);
+        assert!(error.problems[0].message.contains("\"mysql\""), "{}", error.problems[0].message);
+    }
+
+    // 拼错的协议名后面往往是完整的连接地址，错误信息中不能出现密码
+    #[test]
+    fn database_credentials_are_not_echoed() {
+        let dir = config_dir("credentials", &[]);
+        let env = vars(&[("DATABASE_URL", "postgress://user:s3cret@db/notes")]);
+        let error = Settings::load_from(&dir, env, &[]).unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("\"postgress\""), "{}", message);
+        assert!(!message.contains("s3cret") && !message.contains("user"), "{}", message);
+
+        assert_eq!(scheme("user:pw@host/db"), "user");
+        assert_eq!(scheme("//user:pw@host"), "");
+        assert_eq!(scheme("localhost"), "");
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let dir = config_dir(
+            "unknown",
+            &[("development.yaml", "server:\n  prot: 8002\nsever:\n  port: 8003\n")],
+        );
+        let overrides = [set("log.levle", "debug")];
+        let error = Settings::load_from(&dir, vars(&[]), &overrides).unwrap_err();
+        let mut keys = problem_keys(&error);
+        keys.sort();
+        assert_eq!(keys, ["log.levle", "server.prot", "sever.port"]);
+    }
+
+    // 环境变量中其他程序的 APP_ 变量不影响加载
+    #[test]
+    fn unknown_env_vars_are_ignored() {
+        let dir = config_dir("unknown-env", &[]);
+        let settings = Settings::load_from(&dir, vars(&[("APP_NAME", "other-tool")]), &[]).unwrap();
+        assert_eq!(settings.server.port, 8001);
+    }
+
+    #[test]
+    fn parse_override_splits_on_first_equals() {
+        assert_eq!(
+            parse_override("database.url=sqlite://a.db?mode=rwc"),
+            Some(set("database.url", "sqlite://a.db?mode=rwc"))
+        );
+        assert_eq!(parse_override(" server.port =3000"), Some(set("server.port", "3000")));
+        assert_eq!(parse_override("=3000"), None);
+        assert_eq!(parse_override("server.port"), None);
+    }
+}
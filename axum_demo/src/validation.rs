@@ -0,0 +1,180 @@
+// 请求体校验：先用 serde 反序列化，再执行结构体上声明的 validator 规则
+// 所有失败的字段和规则一次性返回 422，方便客户端逐个标注表单错误
+
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{rejection::JsonRejection, FromRequest, Request},
+    Json,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FieldError {
+    // 字段路径，嵌套字段用 . 连接，列表元素用 [i]
+    #[schema(example = "title")]
+    pub field: String,
+    // 规则名，如 required / length / regex / email
+    #[schema(example = "length")]
+    pub rule: String,
+    #[schema(example = "标题不能超过 255 个字符")]
+    pub message: String,
+}
+
+// 用法与 Json<T> 相同，T 需要同时实现 Deserialize 和 Validate
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(request, state).await?;
+        value.validate()?;
+        Ok(ValidatedJson(value))
+    }
+}
+
+impl From<JsonRejection> for AppError {
+    fn from(rejection: JsonRejection) -> Self {
+        AppError::Rejected(rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        AppError::Validation(field_errors(&errors))
+    }
+}
+
+// required 规则：缺省字段经 serde(default) 得到空字符串，只含空白也视为缺失
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("required"));
+    }
+    Ok(())
+}
+
+// 展开嵌套的校验错误，按字段路径排序保证输出稳定
+pub fn field_errors(errors: &ValidationErrors) -> Vec<FieldError> {
+    let mut result = Vec::new();
+    collect(errors, "", &mut result);
+    result
+}
+
+fn collect(errors: &ValidationErrors, prefix: &str, result: &mut Vec<FieldError>) {
+    let sorted: BTreeMap<_, _> = errors.errors().iter().collect();
+    for (field, kind) in sorted {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                // 字段缺失时其他规则的失败没有意义，只报告 required
+                let required = errors.iter().any(|error| error.code == "required");
+                for error in errors.iter().filter(|error| !required || error.code == "required") {
+                    result.push(FieldError {
+                        field: path.clone(),
+                        rule: error.code.to_string(),
+                        message: error
+                            .message
+                            .as_ref()
+                            .map(|m| m.to_string())
+                            .unwrap_or_else(|| format!("{} 未通过 {} 校验", path, error.code)),
+                    });
+                }
+            }
+            ValidationErrorsKind::Struct(nested) => collect(nested, &path, result),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect(nested, &format!("{}[{}]", path, index), result);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{Method, StatusCode};
+    use serde_json::{json, Value};
+
+    use crate::apps::testing::TestApp;
+
+    async fn create_note(body: Value) -> (StatusCode, Value) {
+        let app = TestApp::memory().await;
+        let token = app.token("aa");
+        let response = app.request(Method::POST, "/note", Some(&token), Some(body)).await;
+        (response.status, response.body)
+    }
+
+    #[tokio::test]
+    async fn blank_title_is_required() {
+        let (status, body) = create_note(json!({"title": "  ", "content": "c"})).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body["errors"],
+            json!([{"field": "title", "rule": "required", "message": "标题不能为空"}])
+        );
+    }
+
+    #[tokio::test]
+    async fn over_long_field_reports_length() {
+        let content = "c".repeat(2049);
+        let (status, body) = create_note(json!({"title": "t", "content": content})).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body["errors"],
+            json!([{"field": "content", "rule": "length", "message": "内容不能超过 2048 个字符"}])
+        );
+    }
+
+    // 更新时出现的字段与创建时规则相同，只有空白也不行
+    #[tokio::test]
+    async fn blank_fields_are_rejected_on_update() {
+        let app = TestApp::memory().await;
+        let token = app.token("aa");
+        let created = app
+            .request(Method::POST, "/note", Some(&token), Some(json!({"title": "t", "content": "c"})))
+            .await;
+        let path = format!("/note/{}", created.body["id"].as_str().unwrap());
+
+        let response = app
+            .request(Method::PATCH, &path, Some(&token), Some(json!({"title": "   ", "content": "\n\t"})))
+            .await;
+        assert_eq!(response.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            response.body["errors"],
+            json!([
+                {"field": "content", "rule": "required", "message": "内容不能为空"},
+                {"field": "title", "rule": "required", "message": "标题不能为空"}
+            ])
+        );
+        let note = app.request(Method::GET, &path, Some(&token), None).await;
+        assert_eq!(note.body["title"], "t");
+    }
+
+    // 所有字段的错误一次返回，按字段名排序
+    #[tokio::test]
+    async fn every_failed_field_is_listed() {
+        let (status, body) = create_note(json!({"title": "t".repeat(256)})).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["detail"], "请求参数校验失败 (2 项)");
+        let fields: Vec<_> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| (error["field"].as_str().unwrap(), error["rule"].as_str().unwrap()))
+            .collect();
+        assert_eq!(fields, [("content", "required"), ("title", "length")]);
+    }
+}
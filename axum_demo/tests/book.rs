@@ -0,0 +1,84 @@
+// 课程手册导出 (learn export) 测试：每课一页，目录和翻页链接都指向存在的页面，
+// 页面中包含课程输出与语言对比说明，HTML 正确转义
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use regex::Regex;
+
+// 每个测试导出到自己的目录，并行运行时互不干扰
+fn export(format: &str, name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("axum_demo-book-tests").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    let output = Command::new(env!("CARGO_BIN_EXE_axum_demo"))
+        .args(["learn", "export", "--format", format, "--out"])
+        .arg(&dir)
+        .args(["--lang", "zh"])
+        .env("AXUM_DEMO_DATA_DIR", std::env::temp_dir().join("axum_demo-book-tests/data"))
+        .output()
+        .expect("无法运行 axum_demo");
+    assert!(output.status.success(), "导出失败: {}", String::from_utf8_lossy(&output.stderr));
+    dir
+}
+
+fn read(dir: &Path, name: &str) -> String {
+    fs::read_to_string(dir.join(name)).unwrap_or_else(|e| panic!("无法读取 {}: {}", name, e))
+}
+
+#[test]
+fn markdown_book_links_every_lesson() {
+    let dir = export("md", "md");
+    let index = read(&dir, "index.md");
+    let link = Regex::new(r"\]\(([^)]+)\)").unwrap();
+
+    let pages: Vec<String> = link.captures_iter(&index).map(|c| c[1].to_string()).collect();
+    assert!(!pages.is_empty(), "目录中没有课程链接");
+    assert!(index.contains("## 📚 阶段一：基础语法 (01-20)"), "目录没有按阶段分组:\n{}", index);
+
+    for page in &pages {
+        let text = read(&dir, page);
+        let id = page.trim_end_matches(".md");
+        assert!(text.contains(&format!("# 第{}课：", id)), "{} 缺少课程标题", page);
+        assert!(text.contains("### 可运行示例"), "{} 缺少可运行示例", page);
+        for target in link.captures_iter(&text) {
+            assert!(dir.join(&target[1]).exists(), "{} 链接到不存在的页面 {}", page, &target[1]);
+        }
+    }
+
+    let first = read(&dir, &pages[0]);
+    assert!(first.contains("## 语言对比\n\n```text\n与其他语言对比："), "第一课缺少语言对比:\n{}", first);
+}
+
+#[test]
+fn html_book_is_escaped() {
+    let dir = export("html", "html");
+    let index = read(&dir, "index.html");
+    assert!(dir.join("style.css").exists());
+    assert!(index.starts_with("<!DOCTYPE html>"));
+    // 第23课的要点 "&T, &mut T" 需要转义
+    assert!(index.contains("<a href=\"23.html\">23. 借用与引用</a> - &amp;T, &amp;mut T"), "{}", index);
+
+    let page = read(&dir, "23.html");
+    assert!(page.contains("<h1>第23课：借用与引用</h1>"));
+    assert!(page.contains("<h2>语言对比</h2>\n<pre>"));
+    // 正文中除标签外不应出现未转义的 <
+    let tag = Regex::new(r"</?(!DOCTYPE|html|head|meta|title|link|body|nav|a|h[1-3]|p|pre|ul|li)\b[^>]*>").unwrap();
+    assert!(!tag.replace_all(&page, "").contains('<'), "23.html 有未转义的 <");
+}
+
+#[test]
+fn code_listings_are_highlighted() {
+    let dir = export("html", "listing-html");
+    let page = read(&dir, "84.html");
+    assert!(page.contains("<pre class=\"rust\"><code>"), "84.html 中没有代码清单");
+    assert!(page.contains("<span class=\"kw\">async</span> <span class=\"kw\">fn</span> greet"), "关键字没有高亮");
+    assert!(page.contains("<span class=\"at\">#[tokio::main]</span>"), "属性没有高亮");
+    assert!(page.contains("<span class=\"st\">\"data\"</span>"), "字符串没有高亮");
+
+    let markdown = export("md", "listing-md");
+    let page = read(&markdown, "88.md");
+    assert!(page.contains("```rust\nuse axum::{routing::get, Router};"), "Markdown 中的代码清单应为 rust 代码块");
+}
@@ -0,0 +1,103 @@
+// 命令行测试：退出码 0 成功、1 运行失败、2 用法错误、3 配置无效，
+// 失败时 stderr 中有对应的说明；shell 补全脚本和 man 手册包含所有子命令
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+// 每个测试使用自己的数据目录，不读写真实的学习进度
+fn data_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("axum_demo-cli-tests").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run(data_dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_axum_demo"))
+        .args(args)
+        .args(["--lang", "zh"])
+        .env("AXUM_DEMO_DATA_DIR", data_dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("无法运行 axum_demo")
+}
+
+fn config_dir() -> String {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("config").to_string_lossy().into_owned()
+}
+
+fn assert_exit(output: &Output, code: i32, stderr: &str) {
+    let text = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(code), "{}", text);
+    assert!(text.contains(stderr), "stderr 中没有 {:?}:\n{}", stderr, text);
+}
+
+#[test]
+fn success_is_0() {
+    let output = run(&data_dir("success"), &["learn", "--checklist"]);
+    assert_exit(&output, 0, "");
+}
+
+#[test]
+fn runtime_failure_is_1() {
+    let dir = data_dir("failure");
+    fs::write(dir.join("progress.json"), "{not json").unwrap();
+    let output = run(&dir, &["learn", "progress"]);
+    assert_exit(&output, 1, "无法读取学习进度");
+}
+
+#[test]
+fn usage_errors_are_2() {
+    let dir = data_dir("usage");
+    assert_exit(&run(&dir, &["lern"]), 2, "lern");
+    assert_exit(&run(&dir, &["learn", "99"]), 2, "99");
+    assert_exit(&run(&dir, &["learn", "exercise", "99"]), 2, "99");
+    assert_exit(&run(&dir, &["learn", "exercise", "01"]), 2, "第01课还没有练习");
+    assert_exit(&run(&dir, &["web", "localhost"]), 2, "无效的监听地址");
+}
+
+#[test]
+fn invalid_config_is_3() {
+    let dir = data_dir("config");
+    let output = run(
+        &dir,
+        &["migrate", "--config", &config_dir(), "--set", "log.level=loud", "--set", "server.port=0"],
+    );
+    assert_exit(&output, 3, "配置无效 (2 项)");
+    assert_exit(&output, 3, "log.level");
+
+    let missing = dir.join("no-such-config").to_string_lossy().into_owned();
+    assert_exit(&run(&dir, &["migrate", "--config", &missing]), 3, "");
+}
+
+const COMMANDS: [&str; 6] = ["learn", "web", "openapi", "completions", "manpage", "migrate"];
+const LEARN_ACTIONS: [&str; 5] = ["progress", "next", "exercise", "reset", "export"];
+
+fn stdout_of(output: &Output) -> String {
+    assert_exit(output, 0, "");
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn completions_cover_every_subcommand() {
+    let dir = data_dir("completions");
+    for shell in ["bash", "zsh", "fish"] {
+        let script = stdout_of(&run(&dir, &["completions", shell]));
+        for name in COMMANDS.iter().chain(&LEARN_ACTIONS) {
+            assert!(script.contains(name), "{} 补全脚本中没有 {}", shell, name);
+        }
+    }
+}
+
+// man 手册只列出顶层子命令
+#[test]
+fn manpage_lists_every_subcommand() {
+    let page = stdout_of(&run(&data_dir("manpage"), &["manpage"]));
+    assert!(page.contains(".TH axum_demo 1"), "{}", page);
+    for name in COMMANDS {
+        assert!(page.contains(name), "man 手册中没有 {}", name);
+    }
+}
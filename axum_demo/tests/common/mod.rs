@@ -0,0 +1,37 @@
+// 快照和黄金文件测试共用的比较逻辑
+
+use std::{fs, path::Path};
+
+// 设置了 update_env 环境变量时用 actual 覆盖 path，否则与文件内容比较，
+// 不一致时返回文件路径和第一处不同的行
+pub fn check_snapshot(path: &Path, actual: &str, update_env: &str) -> Result<(), String> {
+    if std::env::var_os(update_env).is_some() {
+        fs::write(path, actual).unwrap();
+        return Ok(());
+    }
+    let expected = fs::read_to_string(path).unwrap_or_default();
+    if actual == expected {
+        return Ok(());
+    }
+    Err(format!("与 {} 不一致\n{}", path.display(), first_difference(&expected, actual)))
+}
+
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => line += 1,
+            (None, None) => return String::new(),
+            (e, a) => {
+                return format!(
+                    "第 {} 行:\n- {}\n+ {}",
+                    line,
+                    e.unwrap_or("<EOF>"),
+                    a.unwrap_or("<EOF>")
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,251 @@
+// 翻译目录检查 (locales/en.toml)
+// 中文是源语言，没有单独的中文目录：代码中的中文格式串就是键，两个方向都要检查
+// - 目录中的每条中文原文都必须还出现在代码中，占位符数量与译文一致 (避免改了代码忘了删目录)
+// - 代码中交给翻译宏的中文格式串 (tr!、lookup 以及课程中的 println! / format! / write! 等) 都必须在目录中
+// - 以 --lang en 运行所有课程、课程列表、学习进度和帮助，以及导出课程手册，输出中不能再有中文 (避免新增文字忘了翻译)
+// 翻译与原文相同的条目表示有意保留中文，检查输出时会先去掉这些文字
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::fs;
+
+use regex::Regex;
+
+type Catalog = BTreeMap<String, BTreeMap<String, String>>;
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn catalog() -> Catalog {
+    let text = fs::read_to_string(manifest_dir().join("locales/en.toml")).unwrap();
+    toml::from_str(&text).expect("locales/en.toml 格式错误")
+}
+
+fn has_chinese(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(c, '\u{3000}'..='\u{303f}' | '\u{4e00}'..='\u{9fff}' | '\u{ff00}'..='\u{ffef}')
+    })
+}
+
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            rust_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+// 代码中的字符串、字符字面量和注释 (/// 是 clap 的帮助文本，// 会出现在课程打印的源码中)，
+// 按运行时的值去掉转义
+fn source_texts() -> HashSet<String> {
+    // 转义可以是 \ 加换行 (续行)
+    let string = Regex::new(r#""((?:[^"\\]|\\(?s:.))*)""#).unwrap();
+    let char_literal = Regex::new(r"'([^'\\])'").unwrap();
+    let comment = Regex::new(r"(?m)//+\s?(.*)$").unwrap();
+
+    let mut files = Vec::new();
+    rust_files(&manifest_dir().join("src"), &mut files);
+    let mut texts = HashSet::new();
+    for file in files {
+        let source = fs::read_to_string(file).unwrap();
+        texts.extend(string.captures_iter(&source).map(|c| unescape(&c[1])));
+        texts.extend(char_literal.captures_iter(&source).map(|c| c[1].to_string()));
+        texts.extend(comment.captures_iter(&source).map(|c| c[1].trim_end().to_string()));
+    }
+    texts
+}
+
+fn unescape(literal: &str) -> String {
+    let mut text = String::new();
+    let mut chars = literal.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => text.push('\n'),
+            Some('t') => text.push('\t'),
+            Some(other) => text.push(other),
+            None => {}
+        }
+    }
+    text
+}
+
+// 格式串中的占位符数量，{{ 和 }} 是转义的括号
+fn placeholders(fmt: &str) -> usize {
+    fmt.replace("{{", "").replace("}}", "").matches('{').count()
+}
+
+#[test]
+fn catalog_matches_source() {
+    let catalog = catalog();
+    let texts = source_texts();
+    let mut problems = Vec::new();
+
+    for (table, messages) in &catalog {
+        if table == "notes" {
+            continue;
+        }
+        for (source, translation) in messages {
+            if !texts.contains(source) {
+                problems.push(format!("[{}] 代码中已没有 {:?}", table, source));
+            }
+            if translation.trim().is_empty() {
+                problems.push(format!("[{}] {:?} 的翻译为空", table, source));
+            }
+            if placeholders(source) != placeholders(translation) {
+                problems.push(format!("[{}] {:?} 与翻译的占位符数量不一致", table, source));
+            }
+        }
+    }
+
+    // 有语言对比说明的课程都需要英文说明
+    let notes = catalog.get("notes").cloned().unwrap_or_default();
+    let lesson_file = Regex::new(r"^_(\d{2})_.*\.rs$").unwrap();
+    for entry in fs::read_dir(manifest_dir().join("src/learn")).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let Some(id) = lesson_file.captures(&name).map(|c| c[1].to_string()) else {
+            continue;
+        };
+        let has_notes = fs::read_to_string(&path).unwrap().contains("\n/*");
+        if has_notes && !notes.contains_key(&id) {
+            problems.push(format!("[notes] 缺少第{}课的说明", id));
+        }
+    }
+
+    assert!(problems.is_empty(), "翻译目录与代码不一致:\n{}", problems.join("\n"));
+}
+
+// 按格式串翻译的调用：tr! 和 lookup 在所有代码中；learn 模块的 println! / print! 经过翻译；
+// 课程模块 (_NN_*.rs) 中 format! / write! 拼出的句子在输出时按格式串反查 (见 src/i18n.rs)，也需要在目录中
+fn translated_literals(path: &Path, source: &str) -> Vec<String> {
+    let name = path.file_name().unwrap().to_string_lossy();
+    let in_learn = path.parent().is_some_and(|dir| dir.ends_with("learn"));
+    let in_lesson = in_learn && name.starts_with('_');
+
+    let call = Regex::new(
+        r#"\b(tr!|lookup|println!|print!|format!|write!)\(\s*(?:[\w.]+\s*,\s*)?"((?:[^"\\]|\\(?s:.))*)""#,
+    )
+    .unwrap();
+    call.captures_iter(source)
+        .filter(|c| match &c[1] {
+            "tr!" | "lookup" => true,
+            "println!" | "print!" => in_learn,
+            _ => in_lesson,
+        })
+        .map(|c| unescape(&c[2]))
+        .filter(|text| has_chinese(text))
+        .collect()
+}
+
+#[test]
+fn source_messages_are_in_catalog() {
+    let keys: HashSet<String> = catalog()
+        .into_iter()
+        .filter(|(table, _)| table != "notes")
+        .flat_map(|(_, messages)| messages.into_keys())
+        .collect();
+
+    let mut files = Vec::new();
+    rust_files(&manifest_dir().join("src"), &mut files);
+    let mut missing = Vec::new();
+    for file in files {
+        let source = fs::read_to_string(&file).unwrap();
+        for text in translated_literals(&file, &source) {
+            if !keys.contains(&text) {
+                missing.push(format!("{}: {:?}", file.strip_prefix(manifest_dir()).unwrap().display(), text));
+            }
+        }
+    }
+
+    assert!(
+        missing.is_empty(),
+        "以下格式串还没有翻译，请在 locales/en.toml 中补上:\n{}",
+        missing.join("\n")
+    );
+}
+
+fn run_english(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_axum_demo"))
+        .args(args)
+        .args(["--lang", "en"])
+        .env("NO_COLOR", "1")
+        .env("AXUM_DEMO_DATA_DIR", std::env::temp_dir().join("axum_demo-i18n-tests"))
+        .output()
+        .expect("无法运行 axum_demo");
+    assert!(
+        output.status.success(),
+        "axum_demo {} 执行失败: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn english_output_has_no_chinese() {
+    // 有意保留的中文，长的先去掉
+    let mut kept: Vec<String> = catalog()
+        .into_values()
+        .flat_map(BTreeMap::into_iter)
+        .filter(|(source, translation)| source == translation)
+        .map(|(_, translation)| translation)
+        .collect();
+    kept.sort_by_key(|text| std::cmp::Reverse(text.len()));
+
+    let ids: Vec<String> = run_english(&["learn", "--checklist"])
+        .lines()
+        .filter_map(|line| line.strip_prefix("- [ ] "))
+        .filter_map(|line| line.split(" - ").next())
+        .map(str::to_string)
+        .collect();
+    assert!(!ids.is_empty(), "没有读取到任何课程");
+
+    let mut commands: Vec<Vec<&str>> = vec![
+        vec!["learn", "--format", "plain"],
+        vec!["learn", "progress", "--format", "plain"],
+        vec!["--help"],
+    ];
+    for subcommand in ["learn", "web", "openapi", "migrate", "completions", "manpage"] {
+        commands.push(vec![subcommand, "--help"]);
+    }
+    for id in &ids {
+        commands.push(vec!["learn", id, "--format", "plain"]);
+    }
+
+    let mut outputs: Vec<(String, String)> = commands
+        .into_iter()
+        .map(|args| (format!("axum_demo {}", args.join(" ")), run_english(&args)))
+        .collect();
+
+    let book = std::env::temp_dir().join("axum_demo-i18n-tests/book");
+    run_english(&["learn", "export", "--format", "md", "--out", book.to_str().unwrap()]);
+    for entry in fs::read_dir(&book).unwrap() {
+        let path = entry.unwrap().path();
+        outputs.push((path.display().to_string(), fs::read_to_string(&path).unwrap()));
+    }
+
+    let mut problems = Vec::new();
+    for (source, output) in outputs {
+        for line in output.lines() {
+            let stripped = kept.iter().fold(line.to_string(), |line, text| line.replace(text.as_str(), ""));
+            if has_chinese(&stripped) {
+                problems.push(format!("{}: {}", source, line));
+            }
+        }
+    }
+
+    assert!(
+        problems.is_empty(),
+        "以下输出还没有翻译，请在 locales/en.toml 中补上:\n{}",
+        problems.join("\n")
+    );
+}
@@ -0,0 +1,201 @@
+// 课程输出的快照测试：逐个运行已登记的课程 (run + main_example)，
+// 与 tests/snapshots/lesson_NN.txt 比较，防止重构时悄悄改变教学输出
+// 确认改动符合预期后，用以下命令更新快照，再用 git diff 审阅:
+//   UPDATE_SNAPSHOTS=1 cargo test --test lessons
+//
+// 每次运行都会变化的内容 (内存地址、耗时、文件描述符、临时目录和仓库中的绝对路径) 在比较前替换为占位符
+// 课程在新建的空目录中运行，当前目录中遗留的文件不会改变输出
+//
+// 第88课的示例服务器 (learn 88 --serve) 另外启动在随机端口上，按课程中的 curl 示例请求一遍
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use regex::Regex;
+
+mod common;
+
+fn snapshot_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots")
+}
+
+// 每次运行一个新的空目录，运行结束后删除
+fn fresh_dir() -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "axum_demo-lesson-tests/cwd-{}-{}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run(args: &[&str]) -> String {
+    let dir = fresh_dir();
+    let output = Command::new(env!("CARGO_BIN_EXE_axum_demo"))
+        .args(args)
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        // 快照是中文输出，不受运行环境的 locale 影响
+        .args(["--lang", "zh"])
+        // 不写入真实的学习进度
+        .env("AXUM_DEMO_DATA_DIR", std::env::temp_dir().join("axum_demo-lesson-tests"))
+        .output()
+        .expect("无法运行 axum_demo");
+    let _ = fs::remove_dir_all(&dir);
+    assert!(
+        output.status.success(),
+        "axum_demo {} 执行失败: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+// 课程编号取自学习清单，新登记的课程会自动加入测试
+fn lesson_ids() -> Vec<String> {
+    run(&["learn", "--checklist"])
+        .lines()
+        .filter_map(|line| line.strip_prefix("- [ ] "))
+        .filter_map(|line| line.split(" - ").next())
+        .map(str::to_string)
+        .collect()
+}
+
+fn redact(text: &str) -> String {
+    let address = Regex::new(r"0x[0-9a-f]{6,}").unwrap();
+    let duration = Regex::new(r"\b\d+(\.\d+)?(ns|µs|us|ms|s)\b").unwrap();
+    let fd = Regex::new(r"\bfd: \d+").unwrap();
+    let path = Regex::new(&format!(
+        r#"(?:{}|{})[^\s"']*"#,
+        regex::escape(&prefix(&std::env::temp_dir())),
+        regex::escape(&prefix(Path::new(env!("CARGO_MANIFEST_DIR"))))
+    ))
+    .unwrap();
+    let text = address.replace_all(text, "0x[addr]");
+    let text = duration.replace_all(&text, "[duration]");
+    let text = fd.replace_all(&text, "fd: [fd]");
+    path.replace_all(&text, "[path]").into_owned()
+}
+
+// 去掉结尾的分隔符，目录本身和其中的路径都能匹配
+fn prefix(dir: &Path) -> String {
+    let dir = dir.to_string_lossy();
+    dir.trim_end_matches(std::path::MAIN_SEPARATOR).to_string()
+}
+
+#[test]
+fn lesson_output_matches_snapshots() {
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let dir = snapshot_dir();
+    fs::create_dir_all(&dir).unwrap();
+
+    let ids = lesson_ids();
+    assert!(!ids.is_empty(), "没有读取到任何课程");
+
+    let mut failures = Vec::new();
+    for id in &ids {
+        let actual = redact(&run(&["learn", id, "--format", "plain"]));
+        let path = dir.join(format!("lesson_{}.txt", id));
+        if let Err(difference) = common::check_snapshot(&path, &actual, "UPDATE_SNAPSHOTS") {
+            failures.push(format!("课程 {} {}", id, difference));
+        }
+    }
+
+    // 已删除课程留下的快照
+    for entry in fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let stale = name
+            .strip_prefix("lesson_")
+            .is_some_and(|id| !ids.iter().any(|known| known == id));
+        if !stale {
+            continue;
+        }
+        if update {
+            fs::remove_file(&path).unwrap();
+        } else {
+            failures.push(format!("{} 没有对应的课程", path.display()));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\n\n确认改动后运行 `UPDATE_SNAPSHOTS=1 cargo test --test lessons` 更新快照",
+        failures.join("\n\n")
+    );
+}
+
+// 发送一个 HTTP/1.1 请求，返回状态码和响应体
+fn request(addr: &str, method: &str, path: &str, body: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        method,
+        path,
+        addr,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response.split(' ').nth(1).and_then(|code| code.parse().ok()).unwrap_or(0);
+    let body = response.split_once("\r\n\r\n").map_or("", |(_, body)| body);
+    (status, body.to_string())
+}
+
+// 测试失败 panic 时也要结束服务器进程，否则它会一直占用端口留在后台
+struct KillOnDrop(Child);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+#[test]
+fn lesson_88_server_handles_user_requests() {
+    let mut child = KillOnDrop(
+        Command::new(env!("CARGO_BIN_EXE_axum_demo"))
+            .args(["learn", "88", "--serve", "--lang", "zh"])
+            .env("PORT", "0")
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("无法运行 axum_demo"),
+    );
+
+    // 第一行输出为监听地址: 🚀 服务器启动在 http://127.0.0.1:端口
+    // 读取端在请求结束前保持打开，否则服务器后续的输出会写入失败
+    let mut stdout = BufReader::new(child.0.stdout.take().unwrap());
+    let mut first_line = String::new();
+    stdout.read_line(&mut first_line).unwrap();
+    let addr = first_line.trim().rsplit("http://").next().unwrap().to_string();
+
+    let responses = [
+        request(&addr, "GET", "/health", ""),
+        request(&addr, "POST", "/users", r#"{"name": "张三", "email": "zhangsan@example.com"}"#),
+        request(&addr, "GET", "/users", ""),
+        request(&addr, "DELETE", "/users/1", ""),
+        request(&addr, "GET", "/users/1", ""),
+    ];
+    drop(child);
+
+    let [health, created, users, deleted, missing] = responses;
+    assert_eq!(health, (200, "服务器运行正常".to_string()));
+    let user = r#"{"id":1,"name":"张三","email":"zhangsan@example.com"}"#;
+    assert_eq!(created, (201, user.to_string()));
+    assert_eq!(users, (200, format!("[{}]", user)));
+    assert_eq!(deleted.0, 204);
+    assert_eq!(missing.0, 404);
+}
@@ -0,0 +1,32 @@
+// OpenAPI 文档的黄金文件测试：路由或模型改动导致文档变化时失败
+// 确认改动符合预期后，用以下命令更新黄金文件:
+//   UPDATE_GOLDEN=1 cargo test --test openapi
+
+use std::{path::PathBuf, process::Command};
+
+mod common;
+
+fn golden_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden/openapi.json")
+}
+
+#[test]
+fn openapi_spec_matches_golden_file() {
+    let output = Command::new(env!("CARGO_BIN_EXE_axum_demo"))
+        .args(["openapi", "--format", "json"])
+        .output()
+        .expect("无法运行 axum_demo openapi");
+    assert!(
+        output.status.success(),
+        "axum_demo openapi 执行失败: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    if let Err(difference) = common::check_snapshot(&golden_path(), &actual, "UPDATE_GOLDEN") {
+        panic!(
+            "OpenAPI 文档{}\n\n确认改动后运行 `UPDATE_GOLDEN=1 cargo test --test openapi` 更新",
+            difference
+        );
+    }
+}